@@ -0,0 +1,70 @@
+//! Times the build of a 10,000-byte invocation for both expansions of the macro, run it with
+//! `cargo bench --bench compile_time`.
+//!
+//! A single interpolated value is enough to fall back to the per-byte array, so both fixtures
+//! go through the macro and only differ in the shape of its expansion.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+const LEN: usize = 10_000;
+
+const RUNS: usize = 5;
+
+fn fixture(splice: bool) -> String {
+    let literals = if splice { LEN - 1 } else { LEN };
+    let mut elements: Vec<String> = (0 .. literals).map(|index| format!("0x{:02X}u8", index % 256)).collect();
+    if splice {
+        elements.push("#SPLICE: u8".to_string());
+    }
+    format!(
+        "use bytify::bytify;\n\npub const SPLICE: u8 = 0xFF;\n\npub const DATA: [u8; {}] = bytify!({});\n",
+        LEN,
+        elements.join(", "),
+    )
+}
+
+fn build(dir: &Path) -> Duration {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let start = Instant::now();
+    let status = Command::new(cargo)
+        .arg("build")
+        .arg("--quiet")
+        .current_dir(dir)
+        .status()
+        .expect("Failed to run cargo");
+    let elapsed = start.elapsed();
+    assert!(status.success(), "Failed to build the fixture");
+    elapsed
+}
+
+fn main() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("compile_time");
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("Cargo.toml"), format!(
+        "[package]\nname = \"compile-time\"\nversion = \"0.0.0\"\nedition = \"2018\"\n\n[dependencies]\nbytify = {{ path = {:?} }}\n\n[workspace]\n",
+        manifest_dir,
+    )).unwrap();
+    // The same versions of the dependencies, so that the fixture builds without a registry update.
+    if let Ok(lock) = fs::read(manifest_dir.join("Cargo.lock")) {
+        fs::write(dir.join("Cargo.lock"), lock).unwrap();
+    }
+    let fixtures = [("byte string", fixture(false)), ("per-byte array", fixture(true))];
+    // Builds the dependencies once, they are not a part of the measurement.
+    fs::write(dir.join("src/lib.rs"), "").unwrap();
+    build(&dir);
+    let mut best = [Duration::MAX; 2];
+    for _ in 0 .. RUNS {
+        for (index, (_, source)) in fixtures.iter().enumerate() {
+            fs::write(dir.join("src/lib.rs"), source).unwrap();
+            best[index] = best[index].min(build(&dir));
+        }
+    }
+    for ((name, _), best) in fixtures.iter().zip(best.iter()) {
+        println!("{}: {} ms, best of {} builds of a {}-byte invocation", name, best.as_millis(), RUNS, LEN);
+    }
+}
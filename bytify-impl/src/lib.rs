@@ -1,13 +1,23 @@
 extern crate proc_macro;
 
+use std::cell::RefCell;
+use std::env::{self, VarError};
+use std::fs;
 use std::io::Error as IOError;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::path::{Path, PathBuf};
 use byteorder::{ByteOrder, WriteBytesExt, BE, LE};
-use failure::Fail;
 use quote::{ToTokens, quote};
+use sha2::{Digest, Sha256};
 use proc_macro::TokenStream;
-use syn::{parse_macro_input, Error as SynError, Expr, IntSuffix, FloatSuffix, Lit, LitInt, LitFloat, Token, UnOp};
+use proc_macro2::{Delimiter, Group, Literal, Spacing, Span, TokenStream as TokenStream2, TokenTree};
+use syn::{braced, bracketed, parenthesized, parse_macro_input, Attribute, BinOp, Data, DeriveInput, Error as SynError, Expr, ExprCall, ExprLit, ExprPath, ExprRange, ExprUnary, FieldValue, Ident, Member, Lit, LitInt, LitFloat, LitStr, Path as SynPath, RangeLimits, Token, Type, UnOp, Visibility};
 use syn::parse::{Parse, ParseStream};
+use syn::token::{Brace, Bracket, Paren};
+use syn::spanned::Spanned;
+use syn::visit_mut::{self, VisitMut};
 use syn::punctuated::Punctuated;
+use thiserror::Error as ThisError;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Endianness {
@@ -21,59 +31,437 @@ const DEFAULT_ENDIANNESS: Endianness = Endianness::LE;
 #[cfg(feature = "default-big-endian")]
 const DEFAULT_ENDIANNESS: Endianness = Endianness::BE;
 
-#[derive(Debug, Fail)]
-enum Error {
-    #[fail(display = "Unsupported prefixed expression in the macro: {} [+] {}", _0, _1)]
-    UnsupportedPrefixedExpression(String, String),
-    #[fail(display = "Unsupported expression in the macro: {}", _0)]
-    UnsupportedExpression(String),
-    #[fail(display = "Unsupported literal in the macro: {}", _0)]
-    UnsupportedLit(String),
-    #[fail(display = "Unsupported numeric suffix in the macro: {}", _0)]
-    UnsupportedNumberSuffix(String),
-    #[fail(display = "Failed to parse the input as a comma-separated list: {}", _0)]
-    InvalidInput(#[cause] SynError),
-    #[fail(display = "Failed to parse endianness: {}", _0)]
-    InvalidEndianness(String),
-    #[fail(display = "Failed to write a suffixed value: {}, negative: {}, given suffix: {}, requested suffix: {}", _0, _1, _2, _3)]
-    IncompatibleNumberSuffix(String, bool, String, String),
-    #[fail(display = "Failed to write a value: {}", _0)]
-    IO(#[cause] IOError),
+#[cfg(target_endian = "little")]
+const NATIVE_ENDIANNESS: Endianness = Endianness::LE;
+
+#[cfg(target_endian = "big")]
+const NATIVE_ENDIANNESS: Endianness = Endianness::BE;
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+const DEFAULT_STRING_ENCODING: (Encoding, Option<Endianness>) = (Encoding::UTF8, None);
+
+#[cfg(feature = "default-utf16le")]
+const DEFAULT_STRING_ENCODING: (Encoding, Option<Endianness>) = (Encoding::UTF16, Some(Endianness::LE));
+
+// Features are additive, so enabling both must still build, the little endian one wins then.
+#[cfg(all(feature = "default-utf16be", not(feature = "default-utf16le")))]
+const DEFAULT_STRING_ENCODING: (Encoding, Option<Endianness>) = (Encoding::UTF16, Some(Endianness::BE));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    UTF8,
+    /// UTF-8 which must not contain anything but ASCII characters.
+    Ascii,
+    UTF16,
+    UTF32,
+    /// Every character is written as a single code point of the given width in bits.
+    CodePoint(u32),
+    Hex,
+    Base64,
+    Base64Url,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Framing {
+    Plain,
+    NulTerminated,
+    LengthPrefixed(u32, Endianness, LengthUnit),
+}
+
+/// Parses the name of a length-delimited block, e.g. `block_u16_be`, into the width and the
+/// endianness of its length prefix, the same as for `len_u16_be` strings.
+fn block_prefix(name: &str) -> Option<(u32, Endianness)> {
+    match length_prefix(&format!("len_{}", name.strip_prefix("block_")?)) {
+        Some((bits, endianness, LengthUnit::Bytes)) => Some((bits, endianness)),
+        _ => None,
+    }
+}
+
+/// Parses the name of a type-length-value record, e.g. `tlv_u16_be`, into the width and the
+/// endianness of its length field, a plain `tlv` has a single byte of length.
+fn tlv_prefix(name: &str) -> Option<(u32, Endianness)> {
+    match name {
+        "tlv" => Some((8, Endianness::BE)),
+        _ => block_prefix(&format!("block_{}", name.strip_prefix("tlv_")?)),
+    }
 }
 
-impl From<SynError> for Error {
+/// What the length prefix of a string counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LengthUnit {
+    /// Bytes of the encoded string.
+    Bytes,
+    /// Unicode scalar values of the string, regardless of the encoding.
+    Chars,
+}
+
+/// Parses the name of a length prefix ascription, e.g. `len_u16_be` or `len_u8_chars`, into the
+/// width, the endianness and the unit of the prefix.
+fn length_prefix(name: &str) -> Option<(u32, Endianness, LengthUnit)> {
+    let (name, unit) = match name.strip_suffix("_chars") {
+        Some(name) => (name, LengthUnit::Chars),
+        None => (name.strip_suffix("_bytes").unwrap_or(name), LengthUnit::Bytes),
+    };
+    let (bits, endianness) = match name {
+        "len_u8" => (8, Endianness::LE),
+        "len_u16_be" => (16, Endianness::BE),
+        "len_u16_le" => (16, Endianness::LE),
+        "len_u32_be" => (32, Endianness::BE),
+        "len_u32_le" => (32, Endianness::LE),
+        _ => {
+            return None;
+        },
+    };
+    Some((bits, endianness, unit))
+}
+
+/// Settings which are inherited by nested elements unless overridden by an ascription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Context {
+    endianness: Endianness,
+    /// Endianness of the string code units if it differs from the one of the numbers, which is
+    /// only the case for a default string encoding set by a feature.
+    string_endianness: Option<Endianness>,
+    encoding: Encoding,
+    framing: Framing,
+    /// The smallest width of negative integers without a suffix, e.g. `i32` for `-1: i32`.
+    signed_width: Option<IntSuffix>,
+    /// The width of non-negative integers without a suffix, e.g. `u16` for `5: u16`.
+    unsigned_width: Option<IntSuffix>,
+    /// Whether the Unicode strings start with a byte order mark.
+    bom: bool,
+    /// Whether the floats written as f32 must not lose any precision, see `f32_strict`.
+    strict_f32: bool,
+}
 
-    fn from(from: SynError) -> Self {
-        Error::InvalidInput(from)
+impl Default for Context {
+
+    fn default() -> Self {
+        Context {
+            endianness: DEFAULT_ENDIANNESS,
+            string_endianness: DEFAULT_STRING_ENCODING.1,
+            encoding: DEFAULT_STRING_ENCODING.0,
+            framing: Framing::Plain,
+            signed_width: None,
+            unsigned_width: None,
+            bom: false,
+            strict_f32: false,
+        }
     }
 }
 
-impl From<IOError> for Error {
+/// Names accepted by `Context::with_ascription`, only listed in the error on an unknown one.
+const ASCRIPTIONS: &[&str] = &[
+    "BE", "be", "big", "NET", "net", "LE", "le", "little", "NE", "ne",
+    "utf8", "utf8_bom", "ascii", "utf16be", "utf16le", "utf16be_bom", "utf16le_bom", "utf32be", "utf32le", "latin1",
+    "u8", "u16", "u32", "hex", "base64", "base64url", "f32_strict", "i8", "i16", "i32", "i64", "i128",
+    "cstr", "wcstr", "wcstr_be", "len_u8", "len_u16_be", "len_u16_le", "len_u32_be", "len_u32_le",
+];
 
-    fn from(from: IOError) -> Self {
-        Error::IO(from)
+/// Tells whether the ascription only selects the endianness.
+fn is_endianness(ident: &Ident) -> bool {
+    matches!(ident.to_string().as_str(), "BE" | "be" | "big" | "NET" | "net" | "LE" | "le" | "little" | "NE" | "ne")
+}
+
+impl Context {
+
+    /// Applies the ascription, e.g. `BE` or `utf16le`, on top of the current settings.
+    fn with_ascription(self, ident: &Ident) -> Result<Self, Error> {
+        let span = ident.span();
+        let context = match ident.to_string().as_str() {
+            "BE" | "be" | "big" | "NET" | "net" => Context { endianness: Endianness::BE, string_endianness: None, ..self },
+            "LE" | "le" | "little" => Context { endianness: Endianness::LE, string_endianness: None, ..self },
+            "NE" | "ne" => Context { endianness: NATIVE_ENDIANNESS, string_endianness: None, ..self },
+            "utf8" => Context { encoding: Encoding::UTF8, bom: false, ..self },
+            "utf8_bom" => Context { encoding: Encoding::UTF8, bom: true, ..self },
+            "ascii" => Context { encoding: Encoding::Ascii, ..self },
+            "utf16be" => Context { endianness: Endianness::BE, string_endianness: None, encoding: Encoding::UTF16, bom: false, ..self },
+            "utf16le" => Context { endianness: Endianness::LE, string_endianness: None, encoding: Encoding::UTF16, bom: false, ..self },
+            "utf16be_bom" => Context { endianness: Endianness::BE, string_endianness: None, encoding: Encoding::UTF16, bom: true, ..self },
+            "utf16le_bom" => Context { endianness: Endianness::LE, string_endianness: None, encoding: Encoding::UTF16, bom: true, ..self },
+            "utf32be" => Context { endianness: Endianness::BE, string_endianness: None, encoding: Encoding::UTF32, bom: false, ..self },
+            "utf32le" => Context { endianness: Endianness::LE, string_endianness: None, encoding: Encoding::UTF32, bom: false, ..self },
+            // ISO-8859-1 maps its bytes to the first 256 code points, which is just a width of 8 bits.
+            "latin1" => Context { encoding: Encoding::CodePoint(8), ..self },
+            // The same widths apply to the characters and to the integers.
+            "u8" => Context { encoding: Encoding::CodePoint(8), unsigned_width: Some(IntSuffix::U8), ..self },
+            "u16" => Context { encoding: Encoding::CodePoint(16), unsigned_width: Some(IntSuffix::U16), ..self },
+            "u32" => Context { encoding: Encoding::CodePoint(32), unsigned_width: Some(IntSuffix::U32), ..self },
+            "hex" => Context { encoding: Encoding::Hex, ..self },
+            "base64" => Context { encoding: Encoding::Base64, ..self },
+            "base64url" => Context { encoding: Encoding::Base64Url, ..self },
+            "f32_strict" => Context { strict_f32: true, ..self },
+            "i8" => Context { signed_width: Some(IntSuffix::I8), ..self },
+            "i16" => Context { signed_width: Some(IntSuffix::I16), ..self },
+            "i32" => Context { signed_width: Some(IntSuffix::I32), ..self },
+            "i64" => Context { signed_width: Some(IntSuffix::I64), ..self },
+            "i128" => Context { signed_width: Some(IntSuffix::I128), ..self },
+            "cstr" => Context { framing: Framing::NulTerminated, ..self },
+            "wcstr" => Context { endianness: Endianness::LE, string_endianness: None, encoding: Encoding::UTF16, bom: false, framing: Framing::NulTerminated, ..self },
+            "wcstr_be" => Context { endianness: Endianness::BE, string_endianness: None, encoding: Encoding::UTF16, bom: false, framing: Framing::NulTerminated, ..self },
+            name if length_prefix(name).is_some() => {
+                let (bits, endianness, unit) = length_prefix(name).unwrap();
+                Context { framing: Framing::LengthPrefixed(bits, endianness, unit), ..self }
+            },
+            unknown => {
+                return Err(Error::UnknownAscription(span, unknown.to_string(), ASCRIPTIONS.join(", ")));
+            },
+        };
+        Ok(context)
     }
 }
 
+#[derive(Debug, ThisError)]
+enum Error {
+    #[error("Unsupported prefixed expression in the macro: {1} [+] {2}")]
+    UnsupportedPrefixedExpression(Span, String, String),
+    #[error("Unsupported expression in the macro: {1}")]
+    UnsupportedExpression(Span, String),
+    #[error("Unsupported literal in the macro: {1}")]
+    UnsupportedLit(Span, String),
+    #[error("Suffixes are only allowed on numbers: {1}, use an ascription instead, e.g. `\"text\": ascii` or `'A': u16`")]
+    SuffixedText(Span, String),
+    #[error("Characters and strings could not be cast: {1}, use {2} to write their code points instead")]
+    TextCast(Span, String, String),
+    #[error("Unsupported numeric suffix in the macro: {1}")]
+    UnsupportedNumberSuffix(Span, String),
+    #[error("Integer literal is out of range: {1}, negative: {2}")]
+    IntegerOutOfRange(Span, String, bool),
+    #[error("Failed to parse the input as a comma-separated list: {0}")]
+    InvalidInput(#[from] SynError),
+    #[error("Failed to parse endianness: {1}")]
+    InvalidEndianness(Span, String),
+    #[error("Unknown ascription: {1}, expected one of {2}, a length prefix could also end with _bytes or _chars")]
+    UnknownAscription(Span, String, String),
+    #[error("Repeat count must be a non-negative integer literal: {1}")]
+    InvalidRepeatCount(Span, String),
+    #[error("Failed to evaluate a constant expression: {1}, reason: {2}")]
+    InvalidConstantExpression(Span, String, String),
+    #[error("Unsupported cast in the macro, expected a numeric type: {1}")]
+    UnsupportedCast(Span, String),
+    #[error("Failed to write a suffixed value: {1}, negative: {2}, given suffix: {3}, requested suffix: {4}")]
+    IncompatibleNumberSuffix(Span, String, bool, String, String),
+    #[error("Integer literal {1} does not fit in {2}, expected {3}")]
+    IntegerOverflow(Span, String, String, String),
+    #[error("Unsigned integer literal could not be negative: {1}, use {2} for a signed value or {3} for its two's complement")]
+    NegativeUnsigned(Span, String, String, String),
+    #[error("Float literal {1} is not exactly representable as f32, negative: {2}")]
+    InexactFloat(Span, String, bool),
+    #[error("Character {1} does not fit in u{2}")]
+    CharOverflow(Span, String, u32),
+    #[error("String must be ASCII: {1}, non-ASCII character {2:?} at index {3}")]
+    NonAscii(Span, String, char, usize),
+    #[error("Failed to write a C string, it already contains a NUL character: {1}")]
+    InteriorNul(Span, String),
+    #[error("String is too long for its length prefix: {1}, length: {2}, prefix: u{3}")]
+    StringTooLong(Span, String, usize, u32),
+    #[error("Failed to decode a string: {1}, reason: {2}")]
+    InvalidEncodedString(Span, String, String),
+    #[error("Failed to include a file: {1}, reason: {2}")]
+    IncludeFailed(Span, String, #[source] IOError),
+    #[error("Failed to read an environment variable: {1}, reason: {2}")]
+    EnvVarFailed(Span, String, #[source] VarError),
+    #[error("Invalid argument of a directive: {1}, reason: {2}")]
+    InvalidDirectiveArgument(Span, String, String),
+    #[error("Interpolated value must be of a numeric type: {1}")]
+    InvalidInterpolation(Span, String),
+    #[error("Endianness has no effect on a byte string, byte or boolean: {1}")]
+    MeaninglessEndianness(Span, String),
+    #[error("Invalid range: {1}, reason: {2}")]
+    InvalidRange(Span, String, String),
+    #[error("Length of the output is {1} bytes, asserted length: {2}")]
+    LengthMismatch(Span, usize, usize),
+    #[error("Bytify could only be derived for structs: {1}")]
+    UnsupportedDeriveInput(Span, String),
+    #[error("Invalid bytify attribute: {1}")]
+    InvalidAttribute(Span, String),
+    #[error("Failed to write a value: {0}")]
+    IO(#[from] IOError),
+    #[error("element {0}: {1}")]
+    InElement(usize, Box<Error>),
+}
+
 impl Error {
 
     pub fn unsupported_expression(expr: Expr) -> Self {
-        Error::UnsupportedExpression(expr.into_token_stream().to_string())
+        Error::UnsupportedExpression(expr.span(), expr.into_token_stream().to_string())
     }
 
     pub fn unsupported_lit(lit: Lit) -> Self {
-        Error::UnsupportedLit(lit.into_token_stream().to_string())
+        Error::UnsupportedLit(lit.span(), lit.into_token_stream().to_string())
     }
 
     pub fn unsupported_prefixed_expression(op: UnOp, expr: Expr) -> Self {
-        Error::UnsupportedPrefixedExpression(op.into_token_stream().to_string(), expr.into_token_stream().to_string())
+        Error::UnsupportedPrefixedExpression(op.span(), op.into_token_stream().to_string(), expr.into_token_stream().to_string())
+    }
+
+    /// Attaches the zero-based index of the failed element, unless it is the only one.
+    pub fn in_element(self, index: usize, len: usize) -> Self {
+        if len > 1 { Error::InElement(index, Box::new(self)) } else { self }
+    }
+
+    /// Reports a value out of the range of its suffix, the range is written in the radix of the
+    /// literal, e.g. `-0x80 ..= 0x7F` for `-0x100i8`.
+    pub fn integer_overflow(negative: bool, int: &Int) -> Self {
+        let repr = if negative { format!("-{}", int.repr) } else { int.repr.clone() };
+        let (bits, signed) = int_suffix_bits(&int.suffix).unwrap_or((128, false));
+        let (min, max) = if signed { (1u128 << (bits - 1), (1u128 << (bits - 1)) - 1) } else { (0, u128::MAX >> (128 - bits)) };
+        let sign = if min == 0 { "" } else { "-" };
+        let range = if int.repr.starts_with("0x") {
+            format!("{}{:#X} ..= {:#X}", sign, min, max).replace("0X", "0x")
+        } else {
+            format!("{}{} ..= {}", sign, min, max)
+        };
+        Error::IntegerOverflow(int.span, repr, format!("{:?}", int.suffix).to_lowercase(), range)
+    }
+
+    /// Reports a negated unsigned value, suggesting the smallest signed suffix fitting it and the
+    /// two's complement in the unsigned width, values beyond `i128` are already out of range.
+    pub fn negative_unsigned(int: &Int) -> Self {
+        let suffix = format!("{:?}", int.suffix).to_lowercase();
+        let digits = int.repr.strip_suffix(&suffix).unwrap_or(&int.repr);
+        let (bits, _) = int_suffix_bits(&int.suffix).unwrap_or((128, false));
+        let signed = [IntSuffix::I8, IntSuffix::I16, IntSuffix::I32, IntSuffix::I64, IntSuffix::I128].iter()
+            .filter(|signed| int_suffix_bits(signed).unwrap().0 >= bits)
+            .find(|signed| int_fits_suffix(true, int.value, signed))
+            .unwrap_or(&IntSuffix::I128);
+        let signed = format!("`-{}{}`", digits, format!("{:?}", signed).to_lowercase());
+        let wrapped = int.value.wrapping_neg() & (u128::MAX >> (128 - bits));
+        let wrapped = if digits.starts_with("0x") {
+            format!("`{:#0width$X}{}`", wrapped, suffix, width = bits as usize / 4 + 2).replace("0X", "0x")
+        } else {
+            format!("`{}{}`", wrapped, suffix)
+        };
+        Error::NegativeUnsigned(int.span, format!("-{}", int.repr), signed, wrapped)
+    }
+
+    pub fn invalid_directive_argument(arg: &Expr, reason: &str) -> Self {
+        Error::InvalidDirectiveArgument(arg.span(), arg.into_token_stream().to_string(), reason.to_string())
+    }
+
+    /// Returns the location of the token which caused the error.
+    pub fn span(&self) -> Span {
+        match self {
+            Error::UnsupportedPrefixedExpression(span, ..) |
+            Error::UnsupportedExpression(span, ..) |
+            Error::UnsupportedLit(span, ..) |
+            Error::SuffixedText(span, ..) |
+            Error::TextCast(span, ..) |
+            Error::UnsupportedNumberSuffix(span, ..) |
+            Error::IntegerOutOfRange(span, ..) |
+            Error::InvalidEndianness(span, ..) |
+            Error::UnknownAscription(span, ..) |
+            Error::InvalidRepeatCount(span, ..) |
+            Error::InvalidConstantExpression(span, ..) |
+            Error::UnsupportedCast(span, ..) |
+            Error::IncompatibleNumberSuffix(span, ..) |
+            Error::IntegerOverflow(span, ..) |
+            Error::NegativeUnsigned(span, ..) |
+            Error::InexactFloat(span, ..) |
+            Error::CharOverflow(span, ..) |
+            Error::NonAscii(span, ..) |
+            Error::InteriorNul(span, ..) |
+            Error::StringTooLong(span, ..) |
+            Error::InvalidEncodedString(span, ..) |
+            Error::IncludeFailed(span, ..) |
+            Error::EnvVarFailed(span, ..) |
+            Error::InvalidDirectiveArgument(span, ..) |
+            Error::InvalidInterpolation(span, ..) |
+            Error::MeaninglessEndianness(span, ..) |
+            Error::InvalidRange(span, ..) |
+            Error::LengthMismatch(span, ..) |
+            Error::UnsupportedDeriveInput(span, ..) |
+            Error::InvalidAttribute(span, ..) => *span,
+            Error::InvalidInput(err) => err.span(),
+            Error::IO(_) => Span::call_site(),
+            Error::InElement(_, err) => err.span(),
+        }
+    }
+
+    /// Converts the error into a `compile_error!` invocation pointing at [`span`].
+    ///
+    /// [`span`]: #method.span
+    pub fn to_compile_error(&self) -> TokenStream2 {
+        SynError::new(self.span(), self).to_compile_error()
+    }
+}
+
+/// Suffix of an integer literal, `None` if there is no suffix at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IntSuffix {
+    None,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+}
+
+impl IntSuffix {
+
+    fn from_lit_int(int: &LitInt) -> Result<Self, Error> {
+        let suffix = match int.suffix() {
+            ""      => IntSuffix::None,
+            "u8"    => IntSuffix::U8,
+            "u16"   => IntSuffix::U16,
+            "u32"   => IntSuffix::U32,
+            "u64"   => IntSuffix::U64,
+            "u128"  => IntSuffix::U128,
+            "usize" => IntSuffix::Usize,
+            "i8"    => IntSuffix::I8,
+            "i16"   => IntSuffix::I16,
+            "i32"   => IntSuffix::I32,
+            "i64"   => IntSuffix::I64,
+            "i128"  => IntSuffix::I128,
+            "isize" => IntSuffix::Isize,
+            suffix => {
+                return Err(Error::UnsupportedNumberSuffix(int.span(), suffix.to_string()));
+            },
+        };
+        Ok(suffix)
+    }
+}
+
+/// An integer literal split into its magnitude and suffix.
+#[derive(Debug, Clone)]
+struct Int {
+    span: Span,
+    repr: String,
+    value: u128,
+    suffix: IntSuffix,
+}
+
+impl Int {
+
+    fn from_lit_int(int: &LitInt) -> Result<Self, Error> {
+        let repr = int.to_string();
+        let value = int.base10_parse::<u128>().map_err(|_| {
+            Error::IntegerOutOfRange(int.span(), repr.clone(), false)
+        })?;
+        Ok(Int {
+            span: int.span(),
+            repr,
+            value,
+            suffix: IntSuffix::from_lit_int(int)?,
+        })
     }
 }
 
-fn int_to_suffix(negative: bool, int: &LitInt) -> Result<IntSuffix, Error> {
-    let num_bits = int.value();
+fn int_to_suffix(negative: bool, int: &Int) -> Result<IntSuffix, Error> {
+    let num_bits = int.value;
     let s = if negative {
         match () {
+            () if num_bits > 0x80000000000000000000000000000000 => {
+                return Err(Error::IntegerOutOfRange(int.span, int.repr.clone(), negative));
+            },
+            () if num_bits > 0x8000000000000000 => IntSuffix::I128,
             () if num_bits > 0x80000000 => IntSuffix::I64,
             () if num_bits > 0x8000     => IntSuffix::I32,
             () if num_bits > 0x80       => IntSuffix::I16,
@@ -81,52 +469,78 @@ fn int_to_suffix(negative: bool, int: &LitInt) -> Result<IntSuffix, Error> {
         }
     } else {
         match () {
+            () if num_bits > 0xFFFFFFFFFFFFFFFF => IntSuffix::U128,
             () if num_bits > 0xFFFFFFFF => IntSuffix::U64,
             () if num_bits > 0xFFFF     => IntSuffix::U32,
             () if num_bits > 0xFF       => IntSuffix::U16,
             () => IntSuffix::U8,
         }
     };
-    let s = match (s, int.suffix()) {
+    // A negative value with an unsigned suffix is not an overflow, leave it to the table below.
+    if let Some((_, signed)) = int_suffix_bits(&int.suffix) {
+        if (signed || !negative) && !int_fits_suffix(negative, num_bits, &int.suffix) {
+            return Err(Error::integer_overflow(negative, int));
+        }
+    }
+    if negative && matches!(int_suffix_bits(&int.suffix), Some((_, false))) {
+        return Err(Error::negative_unsigned(int));
+    }
+    let s = match (s, int.suffix) {
         // If none is specified use the least size suffix possible.
         (s, IntSuffix::None) => s,
         // Allowed casts Uint -> Uint.
-        (IntSuffix::U8 , IntSuffix::U8 ) => IntSuffix::U8 ,
-        (IntSuffix::U8 , IntSuffix::U16) => IntSuffix::U16,
-        (IntSuffix::U8 , IntSuffix::U32) => IntSuffix::U32,
-        (IntSuffix::U8 , IntSuffix::U64) => IntSuffix::U64,
-        (IntSuffix::U16, IntSuffix::U16) => IntSuffix::U16,
-        (IntSuffix::U16, IntSuffix::U32) => IntSuffix::U32,
-        (IntSuffix::U16, IntSuffix::U64) => IntSuffix::U64,
-        (IntSuffix::U32, IntSuffix::U32) => IntSuffix::U32,
-        (IntSuffix::U32, IntSuffix::U64) => IntSuffix::U64,
-        (IntSuffix::U64, IntSuffix::U64) => IntSuffix::U64,
+        (IntSuffix::U8  , IntSuffix::U8  ) => IntSuffix::U8  ,
+        (IntSuffix::U8  , IntSuffix::U16 ) => IntSuffix::U16 ,
+        (IntSuffix::U8  , IntSuffix::U32 ) => IntSuffix::U32 ,
+        (IntSuffix::U8  , IntSuffix::U64 ) => IntSuffix::U64 ,
+        (IntSuffix::U8  , IntSuffix::U128) => IntSuffix::U128,
+        (IntSuffix::U16 , IntSuffix::U16 ) => IntSuffix::U16 ,
+        (IntSuffix::U16 , IntSuffix::U32 ) => IntSuffix::U32 ,
+        (IntSuffix::U16 , IntSuffix::U64 ) => IntSuffix::U64 ,
+        (IntSuffix::U16 , IntSuffix::U128) => IntSuffix::U128,
+        (IntSuffix::U32 , IntSuffix::U32 ) => IntSuffix::U32 ,
+        (IntSuffix::U32 , IntSuffix::U64 ) => IntSuffix::U64 ,
+        (IntSuffix::U32 , IntSuffix::U128) => IntSuffix::U128,
+        (IntSuffix::U64 , IntSuffix::U64 ) => IntSuffix::U64 ,
+        (IntSuffix::U64 , IntSuffix::U128) => IntSuffix::U128,
+        (IntSuffix::U128, IntSuffix::U128) => IntSuffix::U128,
         // Allowed casts Sint -> Sint.
-        (IntSuffix::I8 , IntSuffix::I8 ) => IntSuffix::I8 ,
-        (IntSuffix::I8 , IntSuffix::I16) => IntSuffix::I16,
-        (IntSuffix::I8 , IntSuffix::I32) => IntSuffix::I32,
-        (IntSuffix::I8 , IntSuffix::I64) => IntSuffix::I64,
-        (IntSuffix::I16, IntSuffix::I16) => IntSuffix::I16,
-        (IntSuffix::I16, IntSuffix::I32) => IntSuffix::I32,
-        (IntSuffix::I16, IntSuffix::I64) => IntSuffix::I64,
-        (IntSuffix::I32, IntSuffix::I32) => IntSuffix::I32,
-        (IntSuffix::I32, IntSuffix::I64) => IntSuffix::I64,
-        (IntSuffix::I64, IntSuffix::I64) => IntSuffix::I64,
+        (IntSuffix::I8  , IntSuffix::I8  ) => IntSuffix::I8  ,
+        (IntSuffix::I8  , IntSuffix::I16 ) => IntSuffix::I16 ,
+        (IntSuffix::I8  , IntSuffix::I32 ) => IntSuffix::I32 ,
+        (IntSuffix::I8  , IntSuffix::I64 ) => IntSuffix::I64 ,
+        (IntSuffix::I8  , IntSuffix::I128) => IntSuffix::I128,
+        (IntSuffix::I16 , IntSuffix::I16 ) => IntSuffix::I16 ,
+        (IntSuffix::I16 , IntSuffix::I32 ) => IntSuffix::I32 ,
+        (IntSuffix::I16 , IntSuffix::I64 ) => IntSuffix::I64 ,
+        (IntSuffix::I16 , IntSuffix::I128) => IntSuffix::I128,
+        (IntSuffix::I32 , IntSuffix::I32 ) => IntSuffix::I32 ,
+        (IntSuffix::I32 , IntSuffix::I64 ) => IntSuffix::I64 ,
+        (IntSuffix::I32 , IntSuffix::I128) => IntSuffix::I128,
+        (IntSuffix::I64 , IntSuffix::I64 ) => IntSuffix::I64 ,
+        (IntSuffix::I64 , IntSuffix::I128) => IntSuffix::I128,
+        (IntSuffix::I128, IntSuffix::I128) => IntSuffix::I128,
         // Allowed casts Uint -> Sint.
-        (IntSuffix::U8 , IntSuffix::I8 ) if num_bits < 0x80               => IntSuffix::I8 ,
-        (IntSuffix::U16, IntSuffix::I16) if num_bits < 0x8000             => IntSuffix::I16,
-        (IntSuffix::U32, IntSuffix::I32) if num_bits < 0x80000000         => IntSuffix::I32,
-        (IntSuffix::U64, IntSuffix::I64) if num_bits < 0x8000000000000000 => IntSuffix::I64,
-        (IntSuffix::U8 , IntSuffix::I16) => IntSuffix::I16,
-        (IntSuffix::U8 , IntSuffix::I32) => IntSuffix::I32,
-        (IntSuffix::U8 , IntSuffix::I64) => IntSuffix::I64,
-        (IntSuffix::U16, IntSuffix::I32) => IntSuffix::I32,
-        (IntSuffix::U16, IntSuffix::I64) => IntSuffix::I64,
-        (IntSuffix::U32, IntSuffix::I64) => IntSuffix::I64,
+        (IntSuffix::U8  , IntSuffix::I8  ) if num_bits < 0x80                               => IntSuffix::I8  ,
+        (IntSuffix::U16 , IntSuffix::I16 ) if num_bits < 0x8000                             => IntSuffix::I16 ,
+        (IntSuffix::U32 , IntSuffix::I32 ) if num_bits < 0x80000000                         => IntSuffix::I32 ,
+        (IntSuffix::U64 , IntSuffix::I64 ) if num_bits < 0x8000000000000000                 => IntSuffix::I64 ,
+        (IntSuffix::U128, IntSuffix::I128) if num_bits < 0x80000000000000000000000000000000 => IntSuffix::I128,
+        (IntSuffix::U8  , IntSuffix::I16 ) => IntSuffix::I16 ,
+        (IntSuffix::U8  , IntSuffix::I32 ) => IntSuffix::I32 ,
+        (IntSuffix::U8  , IntSuffix::I64 ) => IntSuffix::I64 ,
+        (IntSuffix::U8  , IntSuffix::I128) => IntSuffix::I128,
+        (IntSuffix::U16 , IntSuffix::I32 ) => IntSuffix::I32 ,
+        (IntSuffix::U16 , IntSuffix::I64 ) => IntSuffix::I64 ,
+        (IntSuffix::U16 , IntSuffix::I128) => IntSuffix::I128,
+        (IntSuffix::U32 , IntSuffix::I64 ) => IntSuffix::I64 ,
+        (IntSuffix::U32 , IntSuffix::I128) => IntSuffix::I128,
+        (IntSuffix::U64 , IntSuffix::I128) => IntSuffix::I128,
         // Everything else is either invalid or ambiguous.
         (given, requested) => {
             return Err(Error::IncompatibleNumberSuffix(
-                int.into_token_stream().to_string(),
+                int.span,
+                int.repr.clone(),
                 negative,
                 format!("{:?}", given),
                 format!("{:?}", requested),
@@ -136,8 +550,8 @@ fn int_to_suffix(negative: bool, int: &LitInt) -> Result<IntSuffix, Error> {
     Ok(s)
 }
 
-fn bytify_implementation_int<O: ByteOrder>(negative: bool, int: LitInt, output: &mut Vec<u8>) -> Result<(), Error> {
-    let num_bits = int.value();
+fn bytify_implementation_int<O: ByteOrder>(negative: bool, int: Int, output: &mut Vec<u8>) -> Result<(), Error> {
+    let num_bits = int.value;
     let num_bits_suffix = int_to_suffix(negative, &int)?;
     match num_bits_suffix {
         IntSuffix::U8 => {
@@ -180,30 +594,132 @@ fn bytify_implementation_int<O: ByteOrder>(negative: bool, int: LitInt, output:
                 output.write_u64::<O>(   num_bits as u64)?;
             }
         },
+        IntSuffix::U128 => {
+            output.write_u128::<O>(num_bits)?;
+        },
+        IntSuffix::I128 => {
+            if negative {
+                output.write_u128::<O>((!num_bits).wrapping_add(1))?;
+            } else {
+                output.write_u128::<O>(  num_bits)?;
+            }
+        },
         // Everything else is either invalid or ambiguous.
         s => {
-            return Err(Error::UnsupportedNumberSuffix(format!("{:?}", s)));
+            return Err(Error::UnsupportedNumberSuffix(int.span, format!("{:?}", s)));
         },
     }
     Ok(())
 }
 
-fn float_to_suffix(negative: bool, float: &LitFloat) -> Result<FloatSuffix, Error> {
-    let num_bits = float.value();
-    let s = if num_bits > 3.40282347e+38 {
-        FloatSuffix::F64
+/// Suffix of a floating point literal, `None` if there is no suffix at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FloatSuffix {
+    None,
+    F16,
+    F32,
+    F64,
+}
+
+impl FloatSuffix {
+
+    fn from_lit_float(float: &LitFloat) -> Result<Self, Error> {
+        let suffix = match float.suffix() {
+            ""    => FloatSuffix::None,
+            "f16" => FloatSuffix::F16,
+            "f32" => FloatSuffix::F32,
+            "f64" => FloatSuffix::F64,
+            suffix => {
+                return Err(Error::UnsupportedNumberSuffix(float.span(), suffix.to_string()));
+            },
+        };
+        Ok(suffix)
+    }
+}
+
+/// A floating point literal split into its magnitude and suffix.
+#[derive(Debug, Clone)]
+struct Float {
+    span: Span,
+    repr: String,
+    value: f64,
+    suffix: FloatSuffix,
+}
+
+impl Float {
+
+    fn from_lit_float(float: &LitFloat) -> Result<Self, Error> {
+        Ok(Float {
+            span: float.span(),
+            repr: float.to_string(),
+            value: float.base10_parse::<f64>()?,
+            suffix: FloatSuffix::from_lit_float(float)?,
+        })
+    }
+}
+
+/// The bits of a positive IEEE 754 half precision infinity.
+const F16_INFINITY: u16 = 0x7C00;
+
+/// Converts the value to IEEE 754 half precision bits, rounding to the nearest even.
+fn f16_bits(value: f64) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 48) & 0x8000) as u16;
+    let exponent = ((bits >> 52) & 0x7FF) as i64 - 1023 + 15;
+    let mantissa = bits & 0x000F_FFFF_FFFF_FFFF;
+    if value == 0.0 {
+        return sign;
+    }
+    if exponent >= 0x1F {
+        return sign | F16_INFINITY;
+    }
+    // Subnormals lose the implicit bit along with a part of the mantissa.
+    let (exponent, mantissa, shift) = if exponent <= 0 {
+        (0, mantissa | (1 << 52), (43 - exponent) as u32)
     } else {
+        (exponent as u64, mantissa, 42)
+    };
+    if shift > 53 {
+        return sign;
+    }
+    let half = 1u64 << (shift - 1);
+    let rest = mantissa & ((1u64 << shift) - 1);
+    let mut result = (exponent << 10) | (mantissa >> shift);
+    if rest > half || (rest == half && result & 1 == 1) {
+        // A carry out of the mantissa correctly bumps the exponent.
+        result += 1;
+    }
+    sign | result as u16
+}
+
+fn float_to_suffix(negative: bool, float: &Float) -> Result<FloatSuffix, Error> {
+    let num_bits = float.value;
+    // Narrowing is only possible if no precision is lost, so `0.1` stays as f64.
+    let s = if f64::from(num_bits as f32) == num_bits {
         FloatSuffix::F32
+    } else {
+        FloatSuffix::F64
     };
-    let s = match (s, float.suffix()) {
-        // If none is specified use the least size suffix possible.
-        (s, FloatSuffix::None) => s,
+    let s = match (s, float.suffix) {
+        // If none is specified the float is always f32, so the width never depends on the value
+        // and a value f32 does not hold exactly needs a suffix.
+        (FloatSuffix::F32, FloatSuffix::None) => FloatSuffix::F32,
+        (_, FloatSuffix::None) => {
+            return Err(Error::InexactFloat(float.span, float.repr.clone(), negative));
+        },
+        (FloatSuffix::F32, FloatSuffix::F32) => FloatSuffix::F32,
+        (FloatSuffix::F64, FloatSuffix::F64) => FloatSuffix::F64,
         // The only possible float cast.
         (FloatSuffix::F32, FloatSuffix::F64) => FloatSuffix::F64,
+        // Loss of precision is fine as long as it is requested explicitly.
+        (FloatSuffix::F64, FloatSuffix::F32) if num_bits <= f64::from(f32::MAX) => FloatSuffix::F32,
+        // Half precision is never picked implicitly, only a value rounding to infinity is rejected.
+        (_, FloatSuffix::F16) if f16_bits(num_bits) != F16_INFINITY => FloatSuffix::F16,
         // Everything else is either invalid or ambiguous.
         (given, requested) => {
             return Err(Error::IncompatibleNumberSuffix(
-                float.into_token_stream().to_string(),
+                float.span,
+                float.repr.clone(),
                 negative,
                 format!("{:?}", given),
                 format!("{:?}", requested),
@@ -213,10 +729,17 @@ fn float_to_suffix(negative: bool, float: &LitFloat) -> Result<FloatSuffix, Erro
     Ok(s)
 }
 
-fn bytify_implementation_float<O: ByteOrder>(negative: bool, float: LitFloat, output: &mut Vec<u8>) -> Result<(), Error> {
-    let num_bits = float.value();
+fn bytify_implementation_float<O: ByteOrder>(negative: bool, float: Float, output: &mut Vec<u8>) -> Result<(), Error> {
+    let num_bits = float.value;
     let num_bits_suffix = float_to_suffix(negative, &float)?;
     match num_bits_suffix {
+        FloatSuffix::F16 => {
+            if negative {
+                output.write_u16::<O>(f16_bits(-num_bits))?;
+            } else {
+                output.write_u16::<O>(f16_bits( num_bits))?;
+            }
+        },
         FloatSuffix::F32 => {
             if negative {
                 output.write_f32::<O>(-(num_bits as f32))?;
@@ -233,27 +756,224 @@ fn bytify_implementation_float<O: ByteOrder>(negative: bool, float: LitFloat, ou
         },
         // Everything else is either invalid or ambiguous.
         s => {
-            return Err(Error::UnsupportedNumberSuffix(format!("{:?}", s)));
+            return Err(Error::UnsupportedNumberSuffix(float.span, format!("{:?}", s)));
+        },
+    }
+    Ok(())
+}
+
+/// Decodes pairs of hex digits, whitespace between the digits is ignored.
+fn decode_hex(string: &str) -> Result<Vec<u8>, String> {
+    let mut digits = Vec::new();
+    for (offset, c) in string.chars().enumerate() {
+        match c.to_digit(16) {
+            Some(digit) => digits.push(digit as u8),
+            None if c.is_whitespace() => {},
+            None => {
+                return Err(format!("invalid hex digit {:?} at offset {}", c, offset));
+            },
+        }
+    }
+    if digits.len() % 2 != 0 {
+        return Err(format!("odd number of hex digits: {}", digits.len()));
+    }
+    Ok(digits.chunks(2).map(|pair| pair[0] << 4 | pair[1]).collect())
+}
+
+/// Decodes a UUID in the canonical `8-4-4-4-12` form, optionally wrapped in braces, into
+/// its RFC 4122 big endian layout.
+fn decode_uuid(string: &str) -> Result<[u8; 16], String> {
+    let trimmed = string.strip_prefix('{').and_then(|rest| rest.strip_suffix('}')).unwrap_or(string);
+    let groups = trimmed.split('-').collect::<Vec<_>>();
+    let lens = groups.iter().map(|group| group.len()).collect::<Vec<_>>();
+    if lens != [8, 4, 4, 4, 12] {
+        return Err("expected the 8-4-4-4-12 form".to_string());
+    }
+    if let Some((offset, c)) = trimmed.chars().enumerate().find(|&(_, c)| c != '-' && !c.is_ascii_hexdigit()) {
+        return Err(format!("invalid hex digit {:?} at offset {}", c, offset));
+    }
+    let mut uuid = [0u8; 16];
+    uuid.copy_from_slice(&decode_hex(&groups.concat())?);
+    Ok(uuid)
+}
+
+/// Decodes base64 with the given alphabet for the last two sextets, padding is optional but
+/// must be correct if present.
+fn decode_base64(string: &str, alphabet: [char; 2]) -> Result<Vec<u8>, String> {
+    let mut sextets = Vec::new();
+    let mut padding = 0;
+    for (offset, c) in string.chars().enumerate() {
+        let sextet = match c {
+            'A' ..= 'Z' => c as u32 - 'A' as u32,
+            'a' ..= 'z' => c as u32 - 'a' as u32 + 26,
+            '0' ..= '9' => c as u32 - '0' as u32 + 52,
+            '=' => {
+                padding += 1;
+                continue;
+            },
+            c if c == alphabet[0] => 62,
+            c if c == alphabet[1] => 63,
+            c => {
+                return Err(format!("invalid base64 character {:?} at offset {}", c, offset));
+            },
+        };
+        if padding > 0 {
+            return Err(format!("unexpected base64 character {:?} after padding at offset {}", c, offset));
+        }
+        sextets.push(sextet);
+    }
+    match (sextets.len() % 4, padding) {
+        (0, 0) | (2, 0) | (2, 2) | (3, 0) | (3, 1) => {},
+        _ => {
+            return Err(format!("invalid base64 length: {}, padding: {}", sextets.len(), padding));
+        },
+    }
+    let mut bytes = Vec::new();
+    for chunk in sextets.chunks(4) {
+        let bits = chunk.iter().fold(0u32, |bits, sextet| bits << 6 | sextet) << (6 * (4 - chunk.len()));
+        let decoded = [(bits >> 16) as u8, (bits >> 8) as u8, bits as u8];
+        bytes.extend_from_slice(&decoded[.. chunk.len() - 1]);
+    }
+    Ok(bytes)
+}
+
+fn bytify_implementation_str<O: ByteOrder>(string: &str, span: Span, encoding: Encoding, output: &mut Vec<u8>) -> Result<(), Error> {
+    match encoding {
+        Encoding::UTF8 => {
+            output.extend_from_slice(string.as_bytes());
+        },
+        Encoding::Ascii => {
+            if let Some((index, c)) = string.chars().enumerate().find(|(_, c)| !c.is_ascii()) {
+                return Err(Error::NonAscii(span, format!("{:?}", string), c, index));
+            }
+            output.extend_from_slice(string.as_bytes());
+        },
+        Encoding::UTF16 => {
+            for unit in string.encode_utf16() {
+                output.write_u16::<O>(unit)?;
+            }
+        },
+        Encoding::UTF32 => {
+            for c in string.chars() {
+                output.write_u32::<O>(c as u32)?;
+            }
+        },
+        Encoding::CodePoint(bits) => {
+            for c in string.chars() {
+                let code_point = c as u32;
+                if u128::from(code_point) > int_mask(bits) {
+                    return Err(Error::CharOverflow(span, format!("{:?}", c), bits));
+                }
+                match bits {
+                    8 => output.write_u8(code_point as u8)?,
+                    16 => output.write_u16::<O>(code_point as u16)?,
+                    _ => output.write_u32::<O>(code_point)?,
+                }
+            }
+        },
+        Encoding::Hex => {
+            let bytes = decode_hex(string).map_err(|reason| {
+                Error::InvalidEncodedString(span, format!("{:?}", string), reason)
+            })?;
+            output.extend_from_slice(&bytes);
+        },
+        Encoding::Base64 | Encoding::Base64Url => {
+            let alphabet = if encoding == Encoding::Base64 { ['+', '/'] } else { ['-', '_'] };
+            let bytes = decode_base64(string, alphabet).map_err(|reason| {
+                Error::InvalidEncodedString(span, format!("{:?}", string), reason)
+            })?;
+            output.extend_from_slice(&bytes);
+        },
+    }
+    Ok(())
+}
+
+/// Writes a length already checked to fit the prefix of the given width.
+fn write_length_prefix(len: usize, bits: u32, endianness: Endianness, output: &mut Vec<u8>) -> Result<(), Error> {
+    match (bits, endianness) {
+        (8, _) => output.write_u8(len as u8)?,
+        (16, Endianness::BE) => output.write_u16::<BE>(len as u16)?,
+        (16, Endianness::LE) => output.write_u16::<LE>(len as u16)?,
+        (_, Endianness::BE) => output.write_u32::<BE>(len as u32)?,
+        (_, Endianness::LE) => output.write_u32::<LE>(len as u32)?,
+    }
+    Ok(())
+}
+
+fn bytify_implementation_string<O: ByteOrder>(string: LitStr, context: Context, output: &mut Vec<u8>) -> Result<(), Error> {
+    let mut value = string.value();
+    // The byte order mark is just U+FEFF encoded the same way as the rest of the string.
+    if context.bom && matches!(context.encoding, Encoding::UTF8 | Encoding::UTF16 | Encoding::UTF32) {
+        value.insert(0, '\u{FEFF}');
+    }
+    match context.framing {
+        Framing::Plain => {
+            bytify_implementation_str::<O>(&value, string.span(), context.encoding, output)?;
+        },
+        Framing::NulTerminated => {
+            if value.contains('\0') {
+                return Err(Error::InteriorNul(string.span(), string.into_token_stream().to_string()));
+            }
+            bytify_implementation_str::<O>(&value, string.span(), context.encoding, output)?;
+            match context.encoding {
+                Encoding::UTF16 | Encoding::CodePoint(16) => output.write_u16::<O>(0)?,
+                Encoding::UTF32 | Encoding::CodePoint(32) => output.write_u32::<O>(0)?,
+                Encoding::UTF8 | Encoding::Ascii | Encoding::CodePoint(_) | Encoding::Hex | Encoding::Base64 | Encoding::Base64Url => output.write_u8(0)?,
+            }
+        },
+        Framing::LengthPrefixed(bits, endianness, unit) => {
+            let mut buffer = Vec::new();
+            bytify_implementation_str::<O>(&value, string.span(), context.encoding, &mut buffer)?;
+            let len = match unit {
+                LengthUnit::Bytes => buffer.len(),
+                LengthUnit::Chars => value.chars().count(),
+            };
+            if len as u128 > int_mask(bits) {
+                return Err(Error::StringTooLong(string.span(), string.into_token_stream().to_string(), len, bits));
+            }
+            write_length_prefix(len, bits, endianness, output)?;
+            output.extend_from_slice(&buffer);
         },
     }
     Ok(())
 }
 
-fn bytify_implementation_element<O: ByteOrder>(lit: Lit, output: &mut Vec<u8>) -> Result<(), Error> {
+fn bytify_implementation_lit<O: ByteOrder>(lit: Lit, context: Context, output: &mut Vec<u8>) -> Result<(), Error> {
+    // The suffixes are only meaningful for numbers.
+    let suffixed = match &lit {
+        Lit::Char(c) => !c.suffix().is_empty(),
+        Lit::Str(string) => !string.suffix().is_empty(),
+        Lit::ByteStr(byte_string) => !byte_string.suffix().is_empty(),
+        Lit::Byte(byte) => !byte.suffix().is_empty(),
+        _ => false,
+    };
+    if suffixed {
+        if let Lit::Char(_) | Lit::Str(_) = lit {
+            return Err(Error::SuffixedText(lit.span(), lit.into_token_stream().to_string()));
+        }
+        return Err(Error::unsupported_lit(lit));
+    }
     match lit {
         Lit::Char(c) => {
-            let offset = output.len();
-            output.resize(c.value().len_utf8() + offset, 0u8);
-            c.value().encode_utf8(&mut output[offset ..]);
+            bytify_implementation_str::<O>(c.value().encode_utf8(&mut [0u8; 4]), c.span(), context.encoding, output)?;
         },
         Lit::Str(string) => {
-            output.extend_from_slice(string.value().as_bytes());
+            bytify_implementation_string::<O>(string, context, output)?;
+        },
+        Lit::ByteStr(byte_string) => {
+            output.extend_from_slice(&byte_string.value());
+        },
+        Lit::Byte(byte) => {
+            output.write_u8(byte.value())?;
+        },
+        Lit::Bool(boolean) => {
+            output.write_u8(if boolean.value { 1 } else { 0 })?;
         },
         Lit::Int(int) => {
-            bytify_implementation_int::<O>(false, int, output)?;
+            bytify_implementation_int::<O>(false, int_with_width(context, false, Int::from_lit_int(&int)?)?, output)?;
         },
         Lit::Float(float) => {
-            bytify_implementation_float::<O>(false, float, output)?;
+            bytify_implementation_float::<O>(false, float_with_strict_narrowing(context, false, Float::from_lit_float(&float)?)?, output)?;
         },
         lit => {
             return Err(Error::unsupported_lit(lit));
@@ -262,99 +982,2472 @@ fn bytify_implementation_element<O: ByteOrder>(lit: Lit, output: &mut Vec<u8>) -
     Ok(())
 }
 
+/// A single element of the list, which is either a Rust expression or something built on
+/// top of the expressions, such as an ascription.
+#[derive(Debug)]
+enum Element {
+    /// A literal or an expression over literals, e.g. `0x10 + 0x20` or `include("logo.bin")`.
+    Expr(Expr),
+    /// A nested array of elements, e.g. `[0x01, 0x02: BE]`.
+    Array(Vec<Element>),
+    /// A repeated element, e.g. `[0xFACEu16: BE; 4]`.
+    Repeat(Box<Element>, Expr),
+    /// An ascribed element, e.g. `9000: LE` or `be { 1u16, 2u32 }`.
+    Ascription(Box<Element>, Ident),
+    /// An interpolated value, e.g. `#VERSION: u16`.
+    Splice(TokenStream2, Type),
+    /// A directive transforming the bytes of the elements inside, e.g. `neg_group(0x0001u16)`
+    /// or `block_u16_be { 1u8, "hi" }`, the value of `tlv(type: 0x01u8, { 1u8 })` is one too.
+    Group(Ident, Vec<Element>),
+    /// An element placed into a fixed-size field, e.g. `field("AB", size: 8, fill: 0x20)`.
+    Field(Ident, Box<Element>, Vec<FieldValue>),
+}
+
+/// Directives taking elements instead of expressions, see [`Element::Group`].
+const GROUP_DIRECTIVES: &[&str] = &["neg_group", "ashex", "ashex_lower"];
+
+impl Parse for Element {
+
+    fn parse(input: ParseStream) -> Result<Self, SynError> {
+        let mut element = Element::parse_unascribed(input)?;
+        // Blocks and interpolated values could already be ascribed with an endianness.
+        let mut endianness = match &element {
+            Element::Ascription(_, ident) if is_endianness(ident) => Some(ident.clone()),
+            _ => None,
+        };
+        // The endianness could also be written right after the value without a colon, e.g. `0x1234u16 be`.
+        if input.peek(Ident) && is_endianness(&input.fork().parse()?) {
+            element = element.ascribed(input.parse()?, &mut endianness)?;
+        }
+        while input.peek(Token![:]) && !input.peek(Token![::]) {
+            input.parse::<Token![:]>()?;
+            let ident: Ident = input.parse()?;
+            // The endianness of a character width could be written right after it, e.g. `'A': u16 LE`.
+            let width = ident == "u8" || ident == "u16" || ident == "u32";
+            element = element.ascribed(ident, &mut endianness)?;
+            if width && input.peek(Ident) {
+                element = element.ascribed(input.parse()?, &mut endianness)?;
+            }
+        }
+        Ok(element)
+    }
+}
+
+impl Element {
+
+    /// Wraps the element into an ascription, a second endianness of the same element is
+    /// rejected instead of being silently overridden.
+    fn ascribed(self, ident: Ident, endianness: &mut Option<Ident>) -> Result<Self, SynError> {
+        if is_endianness(&ident) {
+            if let Some(previous) = endianness {
+                return Err(SynError::new(ident.span(), format!("Conflicting endianness ascription: {}, the element is already ascribed {}", ident, previous)));
+            }
+            *endianness = Some(ident.clone());
+        }
+        Ok(Element::Ascription(Box::new(self), ident))
+    }
+
+    fn parse_unascribed(input: ParseStream) -> Result<Self, SynError> {
+        if input.peek(Token![#]) {
+            input.parse::<Token![#]>()?;
+            let value = if input.peek(Paren) {
+                let content;
+                parenthesized!(content in input);
+                content.parse::<Expr>()?.into_token_stream()
+            } else {
+                input.parse::<SynPath>()?.into_token_stream()
+            };
+            input.parse::<Token![:]>()?;
+            let element = Element::Splice(value, input.parse()?);
+            // The endianness of an interpolated value could be written right after its type.
+            if input.peek(Ident) {
+                return Ok(Element::Ascription(Box::new(element), input.parse()?));
+            }
+            return Ok(element);
+        }
+        // Discriminants are only known to the compiler, so these are interpolated just like `#(Kind::A as u8): u8`.
+        if input.peek(Ident) && input.peek2(Paren) && input.fork().parse::<Ident>()? == "discriminant" {
+            input.parse::<Ident>()?;
+            let content;
+            parenthesized!(content in input);
+            return match parse_ungrouped(&content)? {
+                Expr::Cast(cast_expr) if matches!(*cast_expr.expr, Expr::Path(_)) => {
+                    let ty = (*cast_expr.ty).clone();
+                    Ok(Element::Splice(Expr::Cast(cast_expr).into_token_stream(), ty))
+                },
+                expr => {
+                    Err(SynError::new(expr.span(), "Expected an enum variant cast to an integer, e.g. `discriminant(Kind::A as u8)`"))
+                },
+            };
+        }
+        if input.peek(Ident) && input.peek2(Brace) {
+            let ident: Ident = input.parse()?;
+            let content;
+            braced!(content in input);
+            let elements = Punctuated::<Element, Token![,]>::parse_terminated(&content)?;
+            if block_prefix(&ident.to_string()).is_some() {
+                return Ok(Element::Group(ident, elements.into_iter().collect()));
+            }
+            return Ok(Element::Ascription(Box::new(Element::Array(elements.into_iter().collect())), ident));
+        }
+        if input.peek(Ident) && input.peek2(Paren) && GROUP_DIRECTIVES.contains(&input.fork().parse::<Ident>()?.to_string().as_str()) {
+            let func: Ident = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            let elements = Punctuated::<Element, Token![,]>::parse_terminated(&content)?;
+            return Ok(Element::Group(func, elements.into_iter().collect()));
+        }
+        // The type of a record is written as is, followed by the length and the bytes of the value.
+        if input.peek(Ident) && input.peek2(Paren) && tlv_prefix(&input.fork().parse::<Ident>()?.to_string()).is_some() {
+            let func: Ident = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            if content.peek(Token![type]) {
+                content.parse::<Token![type]>()?;
+                content.parse::<Token![:]>()?;
+            }
+            let ty: Element = content.parse()?;
+            content.parse::<Token![,]>()?;
+            let value;
+            braced!(value in content);
+            let elements = Punctuated::<Element, Token![,]>::parse_terminated(&value)?;
+            if content.peek(Token![,]) {
+                content.parse::<Token![,]>()?;
+            }
+            if !content.is_empty() {
+                return Err(content.error("Expected the end of a record, e.g. `tlv(type: 0x01u8, { 0x02u8 })`"));
+            }
+            return Ok(Element::Array(vec![ty, Element::Group(func, elements.into_iter().collect())]));
+        }
+        // The options of a field are named, so these could not be parsed as arguments of a call.
+        if input.peek(Ident) && input.peek2(Paren) && input.fork().parse::<Ident>()? == "field" {
+            let func: Ident = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            let element: Element = content.parse()?;
+            let mut options = Vec::new();
+            while !content.is_empty() {
+                content.parse::<Token![,]>()?;
+                if content.is_empty() {
+                    break;
+                }
+                options.push(content.parse()?);
+            }
+            return Ok(Element::Field(func, Box::new(element), options));
+        }
+        if input.peek(Ident) && input.peek2(Paren) && matches!(input.fork().parse::<Ident>()?.to_string().as_str(), "fill" | "fill_pattern") {
+            let func: Ident = input.parse()?;
+            let content;
+            let paren_token = parenthesized!(content in input);
+            let mut args = Punctuated::new();
+            args.push_value(parse_ungrouped(&content)?);
+            if content.peek(Token![;]) {
+                content.parse::<Token![;]>()?;
+            } else {
+                content.parse::<Token![,]>()?;
+            }
+            args.push_punct(Token![,](func.span()));
+            args.push_value(parse_ungrouped(&content)?);
+            return Ok(Element::Expr(Expr::Call(ExprCall {
+                attrs: Vec::new(),
+                func: Box::new(Expr::Path(ExprPath {
+                    attrs: Vec::new(),
+                    qself: None,
+                    path: func.into(),
+                })),
+                paren_token,
+                args,
+            })));
+        }
+        if input.peek(Bracket) {
+            let content;
+            bracketed!(content in input);
+            if content.is_empty() {
+                return Ok(Element::Array(Vec::new()));
+            }
+            let element = content.parse()?;
+            if content.peek(Token![;]) {
+                content.parse::<Token![;]>()?;
+                return Ok(Element::Repeat(Box::new(element), parse_ungrouped(&content)?));
+            }
+            let mut elements = vec![element];
+            while !content.is_empty() {
+                content.parse::<Token![,]>()?;
+                if content.is_empty() {
+                    break;
+                }
+                elements.push(content.parse()?);
+            }
+            return Ok(Element::Array(elements));
+        }
+        if input.peek(Paren) {
+            // Parentheses might either be a part of an expression or wrap ascribed elements.
+            let fork = input.fork();
+            let is_expr = fork.parse::<Expr>().is_ok() && (fork.is_empty() || fork.peek(Token![,]) || fork.peek(Token![:]));
+            if !is_expr {
+                let content;
+                parenthesized!(content in input);
+                let mut elements = Punctuated::<Element, Token![,]>::parse_terminated(&content)?;
+                // A single element without a trailing comma is just parenthesized, not a tuple.
+                if elements.len() == 1 && !elements.trailing_punct() {
+                    return Ok(elements.pop().unwrap().into_value());
+                }
+                return Ok(Element::Array(elements.into_iter().collect()));
+            }
+        }
+        Ok(Element::Expr(parse_ungrouped(input)?))
+    }
+}
+
+/// Removes the invisible groups wrapping expressions passed through `$e:expr` of declarative
+/// macros, so these are handled just like the expressions written in place.
+struct Ungroup;
+
+impl VisitMut for Ungroup {
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        while let Expr::Group(group_expr) = expr {
+            *expr = std::mem::replace(&mut *group_expr.expr, Expr::Verbatim(TokenStream2::new()));
+        }
+        visit_mut::visit_expr_mut(self, expr);
+    }
+}
+
+fn parse_ungrouped(input: ParseStream) -> Result<Expr, SynError> {
+    let mut expr = input.parse()?;
+    Ungroup.visit_expr_mut(&mut expr);
+    Ok(expr)
+}
+
+/// Parses the optional `@be;` prefix setting the default ascription of the whole invocation.
+fn parse_default_ascription(input: ParseStream) -> Result<Option<Ident>, SynError> {
+    if !input.peek(Token![@]) {
+        return Ok(None);
+    }
+    input.parse::<Token![@]>()?;
+    let ident = input.parse()?;
+    input.parse::<Token![;]>()?;
+    Ok(Some(ident))
+}
+
 #[derive(Debug)]
 struct MyMacroInput {
-    list: Punctuated<Expr, Token![,]>,
+    ascription: Option<Ident>,
+    list: Punctuated<Element, Token![,]>,
 }
 
 impl Parse for MyMacroInput {
 
     fn parse(input: ParseStream) -> Result<Self, SynError> {
         Ok(MyMacroInput {
-            list: input.parse_terminated(Expr::parse)?,
+            ascription: parse_default_ascription(input)?,
+            list: Punctuated::parse_terminated(input)?,
         })
     }
 }
 
-fn bytify_implementation(input: MyMacroInput) -> Result<TokenStream, Error> {
-    let mut output: Vec<u8> = Vec::new();
-    for expr in input.list {
-        let (
-            endianness,
-            expr,
-        ) = match expr {
-            /* it is not, actually! */ Expr::Type(tpe_expr) => {
-                let expr = *tpe_expr.expr;
-                let endianness = match tpe_expr.ty.into_token_stream().to_string().as_str() {
-                    "BE" | "be" => Endianness::BE,
-                    "LE" | "le" => Endianness::LE,
-                    invalid => {
-                        return Err(Error::InvalidEndianness(invalid.to_string()));
-                    },
-                };
-                (endianness, expr)
-            },
-            expr => {
-                (DEFAULT_ENDIANNESS, expr)
-            },
+#[derive(Debug)]
+struct MyMacroIntoInput {
+    target: Expr,
+    ascription: Option<Ident>,
+    list: Punctuated<Element, Token![,]>,
+}
+
+impl Parse for MyMacroIntoInput {
+
+    fn parse(input: ParseStream) -> Result<Self, SynError> {
+        let target = input.parse()?;
+        let (ascription, list) = if input.is_empty() {
+            (None, Punctuated::new())
+        } else {
+            input.parse::<Token![,]>()?;
+            (parse_default_ascription(input)?, Punctuated::parse_terminated(input)?)
         };
-        match expr {
-            Expr::Lit(lit_expr) => {
-                if endianness == Endianness::BE {
-                    bytify_implementation_element::<BE>(lit_expr.lit, &mut output)?;
-                } else {
-                    bytify_implementation_element::<LE>(lit_expr.lit, &mut output)?;
-                }
-            },
-            Expr::Unary(unary_expr) => {
-                match unary_expr.op {
-                    UnOp::Neg(op) => {
-                        match *unary_expr.expr {
-                            Expr::Lit(lit_expr) => {
-                                match lit_expr.lit {
-                                    Lit::Int(int) => {
-                                        if endianness == Endianness::BE {
-                                            bytify_implementation_int::<BE>(true, int, &mut output)?;
-                                        } else {
-                                            bytify_implementation_int::<LE>(true, int, &mut output)?;
-                                        }
-                                    },
-                                    Lit::Float(float) => {
-                                        if endianness == Endianness::BE {
-                                            bytify_implementation_float::<BE>(true, float, &mut output)?;
-                                        } else {
-                                            bytify_implementation_float::<LE>(true, float, &mut output)?;
-                                        }
-                                    },
-                                    lit => {
-                                        return Err(Error::unsupported_lit(lit));
-                                    },
-                                }
-                            },
-                            expr => {
-                                return Err(Error::unsupported_prefixed_expression(UnOp::Neg(op), expr));
-                            },
-                        }
-                    },
-                    op => {
-                        return Err(Error::unsupported_prefixed_expression(op, *unary_expr.expr));
-                    },
-                }
-            },
-            expr => {
-                return Err(Error::unsupported_expression(expr));
-            },
-        }
+        Ok(MyMacroIntoInput {
+            target,
+            ascription,
+            list,
+        })
     }
-    Ok(quote! {
-        [
-            #(#output),*
-        ]
-    }.into())
 }
 
-#[proc_macro]
-pub fn bytify(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as MyMacroInput);
-    bytify_implementation(input).unwrap_or_else(|err| panic!("{}", err))
+#[derive(Debug)]
+struct MyMacroConstInput {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    ascription: Option<Ident>,
+    list: Punctuated<Element, Token![,]>,
+}
+
+impl Parse for MyMacroConstInput {
+
+    fn parse(input: ParseStream) -> Result<Self, SynError> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        input.parse::<Token![:]>()?;
+        Ok(MyMacroConstInput {
+            attrs,
+            vis,
+            name,
+            ascription: parse_default_ascription(input)?,
+            list: Punctuated::parse_terminated(input)?,
+        })
+    }
+}
+
+/// A named field of `bytify_struct!`, e.g. `version: u16 BE = 1`.
+#[derive(Debug)]
+struct Field {
+    ty: Type,
+    endianness: Option<Ident>,
+    value: Expr,
+}
+
+impl Parse for Field {
+
+    fn parse(input: ParseStream) -> Result<Self, SynError> {
+        // The name is only there for readability.
+        input.parse::<Ident>()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+        let endianness = if input.peek(Ident) { Some(input.parse()?) } else { None };
+        input.parse::<Token![=]>()?;
+        Ok(Field {
+            ty,
+            endianness,
+            value: input.parse()?,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct MyMacroStructInput {
+    ascription: Option<Ident>,
+    fields: Punctuated<Field, Token![,]>,
+}
+
+impl Parse for MyMacroStructInput {
+
+    fn parse(input: ParseStream) -> Result<Self, SynError> {
+        Ok(MyMacroStructInput {
+            ascription: parse_default_ascription(input)?,
+            fields: Punctuated::parse_terminated(input)?,
+        })
+    }
+}
+
+/// Returns the width in bits and the signedness of a sized integer suffix.
+fn int_suffix_bits(suffix: &IntSuffix) -> Option<(u32, bool)> {
+    match suffix {
+        IntSuffix::U8   => Some((8  , false)),
+        IntSuffix::U16  => Some((16 , false)),
+        IntSuffix::U32  => Some((32 , false)),
+        IntSuffix::U64  => Some((64 , false)),
+        IntSuffix::U128 => Some((128, false)),
+        IntSuffix::I8   => Some((8  , true )),
+        IntSuffix::I16  => Some((16 , true )),
+        IntSuffix::I32  => Some((32 , true )),
+        IntSuffix::I64  => Some((64 , true )),
+        IntSuffix::I128 => Some((128, true )),
+        _ => None,
+    }
+}
+
+fn int_fits_suffix(negative: bool, value: u128, suffix: &IntSuffix) -> bool {
+    match int_suffix_bits(suffix) {
+        Some((bits, true)) => {
+            let limit = 1u128 << (bits - 1);
+            if negative { value <= limit } else { value < limit }
+        },
+        Some((bits, false)) => {
+            !negative && (bits == 128 || value < 1u128 << bits)
+        },
+        // Unsized values are only limited by the folding itself.
+        None => true,
+    }
+}
+
+fn fold_add(lhs: (bool, u128), rhs: (bool, u128)) -> Result<(bool, u128), &'static str> {
+    let (lhs_negative, lhs_value) = lhs;
+    let (rhs_negative, rhs_value) = rhs;
+    if lhs_negative == rhs_negative {
+        let value = lhs_value.checked_add(rhs_value).ok_or("attempt to add with overflow")?;
+        Ok((lhs_negative, value))
+    } else if lhs_value >= rhs_value {
+        Ok((lhs_negative, lhs_value - rhs_value))
+    } else {
+        Ok((rhs_negative, rhs_value - lhs_value))
+    }
+}
+
+fn int_mask(bits: u32) -> u128 {
+    if bits == 128 { !0 } else { (1u128 << bits) - 1 }
+}
+
+/// Returns the two's complement bit pattern of a value truncated to the given width.
+fn int_to_bits(negative: bool, value: u128, bits: u32) -> u128 {
+    let mask = int_mask(bits);
+    if negative {
+        (!value).wrapping_add(1) & mask
+    } else {
+        value & mask
+    }
+}
+
+/// Interprets a two's complement bit pattern of the given width.
+fn int_from_bits(pattern: u128, bits: u32, signed: bool) -> (bool, u128) {
+    let mask = int_mask(bits);
+    if signed && (pattern >> (bits - 1)) & 1 == 1 {
+        (true, (!pattern).wrapping_add(1) & mask)
+    } else {
+        (false, pattern & mask)
+    }
+}
+
+/// Returns the width and the signedness of a bitwise operation.
+///
+/// Non-suffixed values behave as if they had an infinite width, so a 128 bit wide signed
+/// pattern is used whenever there is at least one negative operand.
+fn int_bitwise_width(suffix: &IntSuffix, lhs: (bool, u128), rhs: (bool, u128)) -> Result<(u32, bool), &'static str> {
+    match int_suffix_bits(suffix) {
+        Some(width) => Ok(width),
+        None if lhs.0 || rhs.0 => {
+            let fits = |(negative, value): (bool, u128)| int_fits_suffix(negative, value, &IntSuffix::I128);
+            if fits(lhs) && fits(rhs) {
+                Ok((128, true))
+            } else {
+                Err("value is too large for a bitwise operation")
+            }
+        },
+        None => Ok((128, false)),
+    }
+}
+
+fn fold_shift_amount(rhs: (bool, u128), bits: Option<u32>) -> Result<u32, &'static str> {
+    match rhs {
+        (true, _) => Err("attempt to shift by a negative amount"),
+        (false, shift) if shift >= u128::from(bits.unwrap_or(128)) => Err("attempt to shift with overflow"),
+        (false, shift) => Ok(shift as u32),
+    }
+}
+
+fn fold_binary(op: &BinOp, lhs: (bool, u128), rhs: (bool, u128), suffix: &IntSuffix) -> Result<(bool, u128), &'static str> {
+    let (lhs_negative, lhs_value) = lhs;
+    let (rhs_negative, rhs_value) = rhs;
+    let (negative, value) = match op {
+        BinOp::Add(_) => {
+            fold_add(lhs, rhs)?
+        },
+        BinOp::Sub(_) => {
+            fold_add(lhs, (!rhs_negative, rhs_value))?
+        },
+        BinOp::Mul(_) => {
+            let value = lhs_value.checked_mul(rhs_value).ok_or("attempt to multiply with overflow")?;
+            (lhs_negative != rhs_negative, value)
+        },
+        BinOp::Div(_) => {
+            let value = lhs_value.checked_div(rhs_value).ok_or("attempt to divide by zero")?;
+            (lhs_negative != rhs_negative, value)
+        },
+        BinOp::Rem(_) => {
+            let value = lhs_value.checked_rem(rhs_value).ok_or("attempt to calculate the remainder with a divisor of zero")?;
+            (lhs_negative, value)
+        },
+        BinOp::BitAnd(_) | BinOp::BitOr(_) | BinOp::BitXor(_) => {
+            let (bits, signed) = int_bitwise_width(suffix, lhs, rhs)?;
+            let lhs_bits = int_to_bits(lhs_negative, lhs_value, bits);
+            let rhs_bits = int_to_bits(rhs_negative, rhs_value, bits);
+            let pattern = match op {
+                BinOp::BitAnd(_) => lhs_bits & rhs_bits,
+                BinOp::BitOr(_)  => lhs_bits | rhs_bits,
+                _                => lhs_bits ^ rhs_bits,
+            };
+            int_from_bits(pattern, bits, signed)
+        },
+        BinOp::Shl(_) => {
+            match int_suffix_bits(suffix) {
+                Some((bits, signed)) => {
+                    let shift = fold_shift_amount(rhs, Some(bits))?;
+                    int_from_bits(int_to_bits(lhs_negative, lhs_value, bits) << shift, bits, signed)
+                },
+                None => {
+                    let shift = fold_shift_amount(rhs, None)?;
+                    if lhs_value.leading_zeros() < shift {
+                        return Err("attempt to shift left with overflow");
+                    }
+                    (lhs_negative, lhs_value << shift)
+                },
+            }
+        },
+        BinOp::Shr(_) => {
+            match int_suffix_bits(suffix) {
+                Some((bits, signed)) => {
+                    let shift = fold_shift_amount(rhs, Some(bits))?;
+                    let pattern = int_to_bits(lhs_negative, lhs_value, bits);
+                    let pattern = if signed && lhs_negative {
+                        // Sign extension for the arithmetic shift.
+                        (((pattern | !int_mask(bits)) as i128 >> shift) as u128) & int_mask(bits)
+                    } else {
+                        pattern >> shift
+                    };
+                    int_from_bits(pattern, bits, signed)
+                },
+                None => {
+                    let shift = fold_shift_amount(rhs, None)?;
+                    if lhs_negative {
+                        // Rounds towards negative infinity, just like the arithmetic shift does.
+                        let rounded = (lhs_value >> shift) + if lhs_value & int_mask(shift) != 0 { 1 } else { 0 };
+                        (true, rounded)
+                    } else {
+                        (false, lhs_value >> shift)
+                    }
+                },
+            }
+        },
+        _ => {
+            return Err("unsupported operator");
+        },
+    };
+    // There is no such thing as a negative zero for integers.
+    Ok((negative && value != 0, value))
+}
+
+fn fold_not(negative: bool, value: u128, suffix: &IntSuffix) -> Result<(bool, u128), &'static str> {
+    match int_suffix_bits(suffix) {
+        Some((bits, signed)) => {
+            Ok(int_from_bits(!int_to_bits(negative, value, bits), bits, signed))
+        },
+        // !x == -x - 1 for an infinitely wide two's complement.
+        None if negative => {
+            Ok((false, value - 1))
+        },
+        None => {
+            let value = value.checked_add(1).ok_or("value is too large for a bitwise operation")?;
+            Ok((true, value))
+        },
+    }
+}
+
+fn int_suffix_from_type(ty: &Type) -> Option<IntSuffix> {
+    let suffix = match ty.into_token_stream().to_string().as_str() {
+        "u8"   => IntSuffix::U8,
+        "u16"  => IntSuffix::U16,
+        "u32"  => IntSuffix::U32,
+        "u64"  => IntSuffix::U64,
+        "u128" => IntSuffix::U128,
+        "i8"   => IntSuffix::I8,
+        "i16"  => IntSuffix::I16,
+        "i32"  => IntSuffix::I32,
+        "i64"  => IntSuffix::I64,
+        "i128" => IntSuffix::I128,
+        _ => {
+            return None;
+        },
+    };
+    Some(suffix)
+}
+
+/// Evaluates an arithmetic or bitwise expression over integer literals.
+///
+/// Suffixed operands are checked against the range of their type after every operation,
+/// while non-suffixed ones are only limited by 128 bits of magnitude, the resulting value
+/// is then written like any other literal. Casts truncate the value just like `as` does.
+fn fold_int(expr: Expr) -> Result<(bool, Int), Error> {
+    let span = expr.span();
+    let repr = expr.clone().into_token_stream().to_string();
+    let (negative, value, suffix) = match expr {
+        Expr::Lit(lit_expr) => {
+            let int = match lit_expr.lit {
+                Lit::Int(int) => {
+                    Int::from_lit_int(&int)?
+                },
+                lit => {
+                    return Err(Error::unsupported_lit(lit));
+                },
+            };
+            // The magnitude is checked right away, even before a cast, while the sign is only
+            // known once the literal is used, so `-128i8` is still valid.
+            let signed = matches!(int_suffix_bits(&int.suffix), Some((_, true)));
+            if int.suffix != IntSuffix::None && !int_fits_suffix(signed, int.value, &int.suffix) {
+                return Err(Error::integer_overflow(false, &int));
+            }
+            return Ok((false, int));
+        },
+        Expr::Paren(paren_expr) => {
+            let (negative, int) = fold_int(*paren_expr.expr)?;
+            (negative, int.value, int.suffix)
+        },
+        Expr::Group(group_expr) => {
+            let (negative, int) = fold_int(*group_expr.expr)?;
+            (negative, int.value, int.suffix)
+        },
+        Expr::Unary(unary_expr) => {
+            match unary_expr.op {
+                UnOp::Neg(_) => {
+                    let (negative, int) = fold_int(*unary_expr.expr)?;
+                    (!negative && int.value != 0, int.value, int.suffix)
+                },
+                UnOp::Not(_) => {
+                    let (negative, int) = fold_int(*unary_expr.expr)?;
+                    let (negative, value) = fold_not(negative, int.value, &int.suffix)
+                        .map_err(|reason| Error::InvalidConstantExpression(span, repr.clone(), reason.to_string()))?;
+                    (negative, value, int.suffix)
+                },
+                op => {
+                    return Err(Error::unsupported_prefixed_expression(op, *unary_expr.expr));
+                },
+            }
+        },
+        Expr::Binary(binary_expr) => {
+            let (lhs_negative, lhs) = fold_int(*binary_expr.left)?;
+            let (rhs_negative, rhs) = fold_int(*binary_expr.right)?;
+            let suffix = match (&binary_expr.op, lhs.suffix, rhs.suffix) {
+                // The shift amount is allowed to be of any type.
+                (BinOp::Shl(_), suffix, _) | (BinOp::Shr(_), suffix, _) => suffix,
+                (_, IntSuffix::None, suffix) | (_, suffix, IntSuffix::None) => suffix,
+                (_, lhs_suffix, rhs_suffix) => {
+                    if lhs_suffix != rhs_suffix {
+                        return Err(Error::InvalidConstantExpression(
+                            span,
+                            repr,
+                            format!("mismatched suffixes {:?} and {:?}", lhs_suffix, rhs_suffix),
+                        ));
+                    }
+                    lhs_suffix
+                },
+            };
+            if !int_fits_suffix(lhs_negative, lhs.value, &suffix) {
+                return Err(Error::InvalidConstantExpression(span, repr, format!("{} does not fit into {:?}", lhs.repr, suffix)));
+            }
+            let is_shift = matches!(binary_expr.op, BinOp::Shl(_) | BinOp::Shr(_));
+            if !is_shift && !int_fits_suffix(rhs_negative, rhs.value, &suffix) {
+                return Err(Error::InvalidConstantExpression(span, repr, format!("{} does not fit into {:?}", rhs.repr, suffix)));
+            }
+            let (negative, value) = fold_binary(&binary_expr.op, (lhs_negative, lhs.value), (rhs_negative, rhs.value), &suffix)
+                .map_err(|reason| Error::InvalidConstantExpression(span, repr.clone(), reason.to_string()))?;
+            (negative, value, suffix)
+        },
+        Expr::Cast(cast_expr) => {
+            let suffix = match int_suffix_from_type(&cast_expr.ty) {
+                Some(suffix) => suffix,
+                None => {
+                    return Err(Error::UnsupportedCast(cast_expr.ty.span(), cast_expr.ty.into_token_stream().to_string()));
+                },
+            };
+            if let Expr::Lit(ExprLit { lit: Lit::Char(_), .. }) | Expr::Lit(ExprLit { lit: Lit::Str(_), .. }) = *cast_expr.expr {
+                let lit = cast_expr.expr.into_token_stream().to_string();
+                let advice = match suffix {
+                    IntSuffix::U8 => format!("`{}: u8` or `{}: ascii`", lit, lit),
+                    IntSuffix::U16 | IntSuffix::U32 => format!("`{}: {}`", lit, format!("{:?}", suffix).to_lowercase()),
+                    _ => format!("`{}: u8`, `{}: u16` or `{}: u32`", lit, lit, lit),
+                };
+                return Err(Error::TextCast(span, repr, advice));
+            }
+            let (bits, signed) = int_suffix_bits(&suffix).unwrap_or((128, false));
+            let (negative, value) = if is_float_expr(&cast_expr.expr) {
+                let (negative, float) = fold_float(*cast_expr.expr)?;
+                float_to_int_saturating(if negative { -float.value } else { float.value }, bits, signed)
+            } else {
+                let (negative, int) = fold_int(*cast_expr.expr)?;
+                int_from_bits(int_to_bits(negative, int.value, bits), bits, signed)
+            };
+            (negative, value, suffix)
+        },
+        Expr::Call(call_expr) if call_expr.func.to_token_stream().to_string() == "parse" => {
+            let (negative, value) = fold_parse(&call_expr)?;
+            (negative, value, IntSuffix::None)
+        },
+        expr => {
+            return Err(Error::unsupported_expression(expr));
+        },
+    };
+    if !int_fits_suffix(negative, value, &suffix) {
+        return Err(Error::InvalidConstantExpression(span, repr, format!("value does not fit into {:?}", suffix)));
+    }
+    Ok((negative, Int {
+        span,
+        repr,
+        value,
+        suffix,
+    }))
+}
+
+/// Drops the optional `base:` label of the `parse("FF", base: 16)` directive, so that its arguments
+/// parse as those of a plain call wherever the directive is nested.
+fn unlabel_parse_base(tokens: TokenStream2) -> TokenStream2 {
+    let mut output: Vec<TokenTree> = Vec::new();
+    for token in tokens {
+        let token = match token {
+            TokenTree::Group(group) => {
+                let is_parse = matches!(output.last(), Some(TokenTree::Ident(ident)) if ident == "parse");
+                let mut stream = unlabel_parse_base(group.stream());
+                if is_parse && group.delimiter() == Delimiter::Parenthesis {
+                    let args: Vec<TokenTree> = stream.into_iter().collect();
+                    let mut unlabeled: Vec<TokenTree> = Vec::new();
+                    let mut index = 0;
+                    while index < args.len() {
+                        let labeled = matches!(unlabeled.last(), Some(TokenTree::Punct(comma)) if comma.as_char() == ',')
+                            && matches!(&args[index], TokenTree::Ident(ident) if ident == "base")
+                            && matches!(args.get(index + 1), Some(TokenTree::Punct(colon)) if colon.as_char() == ':' && colon.spacing() == Spacing::Alone);
+                        if labeled {
+                            index += 2;
+                            continue;
+                        }
+                        unlabeled.push(args[index].clone());
+                        index += 1;
+                    }
+                    stream = unlabeled.into_iter().collect();
+                }
+                let mut unlabeled = Group::new(group.delimiter(), stream);
+                unlabeled.set_span(group.span());
+                TokenTree::Group(unlabeled)
+            },
+            token => token,
+        };
+        output.push(token);
+    }
+    output.into_iter().collect()
+}
+
+/// Parses the string of the `parse("FF", 16)` directive as an integer in the given base, which
+/// is 10 by default.
+fn fold_parse(call_expr: &ExprCall) -> Result<(bool, u128), Error> {
+    let args = call_expr.args.iter().collect::<Vec<_>>();
+    let (string, base) = match args.as_slice() {
+        [Expr::Lit(ExprLit { lit: Lit::Str(string), .. }), base @ ..] if base.len() <= 1 => (string, base.first()),
+        _ => {
+            return Err(Error::unsupported_expression(Expr::Call(call_expr.clone())));
+        },
+    };
+    let base = match base {
+        Some(base) => match directive_len(base)? {
+            base @ 2 ..= 36 => base as u32,
+            _ => {
+                return Err(Error::invalid_directive_argument(base, "base must be between 2 and 36"));
+            },
+        },
+        None => 10,
+    };
+    let value = string.value();
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value.strip_prefix('+').unwrap_or(&value)),
+    };
+    let value = u128::from_str_radix(digits, base).map_err(|err| {
+        Error::InvalidEncodedString(string.span(), format!("{:?}", string.value()), err.to_string())
+    })?;
+    Ok((negative && value != 0, value))
+}
+
+fn float_suffix_from_type(ty: &Type) -> Option<FloatSuffix> {
+    match ty.into_token_stream().to_string().as_str() {
+        "f16" => Some(FloatSuffix::F16),
+        "f32" => Some(FloatSuffix::F32),
+        "f64" => Some(FloatSuffix::F64),
+        _ => None,
+    }
+}
+
+/// Tells whether the expression is a string, so `*` repeats it rather than multiplies.
+fn is_string_expr(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(lit_expr) => matches!(lit_expr.lit, Lit::Str(_) | Lit::ByteStr(_)),
+        Expr::Paren(paren_expr) => is_string_expr(&paren_expr.expr),
+        Expr::Group(group_expr) => is_string_expr(&group_expr.expr),
+        _ => false,
+    }
+}
+
+/// Tells whether the expression evaluates to a float rather than an integer.
+fn is_float_expr(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(lit_expr) => matches!(lit_expr.lit, Lit::Float(_)),
+        Expr::Paren(paren_expr) => is_float_expr(&paren_expr.expr),
+        Expr::Group(group_expr) => is_float_expr(&group_expr.expr),
+        Expr::Unary(unary_expr) => is_float_expr(&unary_expr.expr),
+        Expr::Cast(cast_expr) => float_suffix_from_type(&cast_expr.ty).is_some(),
+        _ => false,
+    }
+}
+
+/// Converts a float into an integer of the given width the same way `as` does, i.e.
+/// rounding towards zero and saturating at the bounds of the type.
+fn float_to_int_saturating(value: f64, bits: u32, signed: bool) -> (bool, u128) {
+    if signed {
+        let max = int_mask(bits - 1) as i128;
+        let value = (value as i128).max(-max - 1).min(max);
+        (value < 0, value.unsigned_abs())
+    } else {
+        (false, (value as u128).min(int_mask(bits)))
+    }
+}
+
+/// Evaluates a float literal, optionally negated or cast from another number.
+fn fold_float(expr: Expr) -> Result<(bool, Float), Error> {
+    let span = expr.span();
+    let repr = expr.clone().into_token_stream().to_string();
+    match expr {
+        Expr::Lit(lit_expr) => {
+            match lit_expr.lit {
+                Lit::Float(float) => {
+                    Ok((false, Float::from_lit_float(&float)?))
+                },
+                lit => {
+                    Err(Error::unsupported_lit(lit))
+                },
+            }
+        },
+        Expr::Paren(paren_expr) => {
+            fold_float(*paren_expr.expr)
+        },
+        Expr::Group(group_expr) => {
+            fold_float(*group_expr.expr)
+        },
+        Expr::Unary(unary_expr) => {
+            match unary_expr.op {
+                UnOp::Neg(_) => {
+                    let (negative, float) = fold_float(*unary_expr.expr)?;
+                    Ok((!negative, float))
+                },
+                op => {
+                    Err(Error::unsupported_prefixed_expression(op, *unary_expr.expr))
+                },
+            }
+        },
+        Expr::Cast(cast_expr) => {
+            let suffix = match float_suffix_from_type(&cast_expr.ty) {
+                Some(suffix) => suffix,
+                None => {
+                    return Err(Error::UnsupportedCast(cast_expr.ty.span(), cast_expr.ty.into_token_stream().to_string()));
+                },
+            };
+            let (negative, value) = if is_float_expr(&cast_expr.expr) {
+                let (negative, float) = fold_float(*cast_expr.expr)?;
+                (negative, float.value)
+            } else {
+                let (negative, int) = fold_int(*cast_expr.expr)?;
+                (negative, int.value as f64)
+            };
+            Ok((negative, Float {
+                span,
+                repr,
+                value,
+                suffix,
+            }))
+        },
+        expr => {
+            Err(Error::unsupported_expression(expr))
+        },
+    }
+}
+
+/// Returns the integer if the expression is a plain integer literal.
+fn int_literal(expr: &Expr) -> Option<Int> {
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Int(int), .. }) => Int::from_lit_int(int).ok(),
+        _ => None,
+    }
+}
+
+fn repeat_len(expr: Expr) -> Result<usize, Error> {
+    match int_literal(&expr) {
+        Some(Int { value, suffix: IntSuffix::None, .. }) |
+        Some(Int { value, suffix: IntSuffix::Usize, .. }) if value <= usize::MAX as u128 => {
+            Ok(value as usize)
+        },
+        _ => {
+            Err(Error::InvalidRepeatCount(expr.span(), expr.into_token_stream().to_string()))
+        },
+    }
+}
+
+/// Resolves the path relative to the source file invoking the macro, falling back to the
+/// manifest directory when the source file is not known.
+fn include_path(path: &str) -> PathBuf {
+    let base = proc_macro::Span::call_site()
+        .local_file()
+        .and_then(|file| file.parent().map(Path::to_path_buf))
+        .or_else(|| env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from))
+        .unwrap_or_default();
+    base.join(path)
+}
+
+/// Something the macro reads on its own, the expansion has to refer to it for the compiler to
+/// rebuild the crate when it changes.
+enum Tracked {
+    File(String),
+    Env(String),
+    /// The offset and the length of every top level element, only there to be looked at.
+    #[cfg(feature = "trace")]
+    Trace(String),
+}
+
+impl ToTokens for Tracked {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            Tracked::File(path) => {
+                tokens.extend(quote!(const _: &[u8] = include_bytes!(#path);));
+            },
+            Tracked::Env(name) => {
+                tokens.extend(quote!(const _: Option<&str> = option_env!(#name);));
+            },
+            #[cfg(feature = "trace")]
+            Tracked::Trace(table) => {
+                tokens.extend(quote!(#[doc(hidden)] const _: &str = #table;));
+            },
+        }
+    }
+}
+
+thread_local! {
+    // Plain data rather than tokens, which may not outlive the macro invocation.
+    static TRACKED: RefCell<Vec<Tracked>> = const { RefCell::new(Vec::new()) };
+}
+
+fn track(item: Tracked) {
+    TRACKED.with(|tracked| tracked.borrow_mut().push(item));
+}
+
+fn take_tracked() -> Vec<Tracked> {
+    TRACKED.with(|tracked| tracked.take())
+}
+
+fn directive_len(arg: &Expr) -> Result<usize, Error> {
+    match int_literal(arg) {
+        Some(Int { value, suffix: IntSuffix::None, .. }) |
+        Some(Int { value, suffix: IntSuffix::Usize, .. }) if value <= usize::MAX as u128 => {
+            Ok(value as usize)
+        },
+        _ => {
+            Err(Error::invalid_directive_argument(arg, "expected a non-negative integer literal"))
+        },
+    }
+}
+
+fn directive_bool(arg: &Expr) -> Result<bool, Error> {
+    match arg {
+        Expr::Lit(ExprLit { lit: Lit::Bool(boolean), .. }) => {
+            Ok(boolean.value)
+        },
+        _ => {
+            Err(Error::invalid_directive_argument(arg, "expected a boolean literal"))
+        },
+    }
+}
+
+fn directive_byte(arg: &Expr) -> Result<u8, Error> {
+    match int_literal(arg) {
+        Some(Int { value, suffix: IntSuffix::None, .. }) |
+        Some(Int { value, suffix: IntSuffix::U8, .. }) if value <= 0xFF => {
+            Ok(value as u8)
+        },
+        _ => {
+            Err(Error::invalid_directive_argument(arg, "expected a byte integer literal"))
+        },
+    }
+}
+
+/// Parses the name of a fixed point directive, e.g. `fixed_q8_8`, into the number of fractional
+/// bits and the suffix of the whole signed value.
+fn fixed_point_format(name: &str) -> Option<(u32, IntSuffix)> {
+    let (integer, fraction) = name.strip_prefix("fixed_q")?.split_once('_')?;
+    let integer = integer.parse::<u32>().ok()?;
+    let fraction = fraction.parse::<u32>().ok()?;
+    let suffix = match integer.checked_add(fraction)? {
+        8  => IntSuffix::I8,
+        16 => IntSuffix::I16,
+        32 => IntSuffix::I32,
+        64 => IntSuffix::I64,
+        _ => {
+            return None;
+        },
+    };
+    Some((fraction, suffix))
+}
+
+fn bytify_implementation_fixed<O: ByteOrder>(arg: &Expr, fraction: u32, suffix: IntSuffix, output: &mut Vec<u8>) -> Result<(), Error> {
+    let (negative, value) = if is_float_expr(arg) {
+        let (negative, float) = fold_float(arg.clone())?;
+        (negative, float.value)
+    } else {
+        let (negative, int) = fold_int(arg.clone())?;
+        (negative, int.value as f64)
+    };
+    let scaled = (value * 2f64.powi(fraction as i32)).round();
+    let bits = int_suffix_bits(&suffix).map_or(0, |(bits, _)| bits);
+    let limit = 2f64.powi(bits as i32 - 1);
+    let fits = if negative { scaled <= limit } else { scaled < limit };
+    if !fits {
+        return Err(Error::invalid_directive_argument(arg, &format!("value does not fit in Q{}.{}", bits - fraction, fraction)));
+    }
+    bytify_implementation_int::<O>(negative && scaled != 0.0, Int {
+        span: arg.span(),
+        repr: arg.into_token_stream().to_string(),
+        value: scaled as u128,
+        suffix,
+    }, output)
+}
+
+/// Writes the exact bit pattern of a float given as an unsigned integer of the same width.
+fn bytify_implementation_float_bits<O: ByteOrder>(arg: &Expr, suffix: IntSuffix, output: &mut Vec<u8>) -> Result<(), Error> {
+    let (negative, int) = fold_int(arg.clone())?;
+    let bits = int_suffix_bits(&suffix).map_or(0, |(bits, _)| bits);
+    if negative || (int.suffix != IntSuffix::None && int.suffix != suffix) {
+        return Err(Error::invalid_directive_argument(arg, &format!("expected a u{} bit pattern", bits)));
+    }
+    if !int_fits_suffix(false, int.value, &suffix) {
+        return Err(Error::invalid_directive_argument(arg, &format!("bit pattern does not fit in {} bits", bits)));
+    }
+    bytify_implementation_int::<O>(false, Int { suffix, ..int }, output)
+}
+
+/// Writes a signed integer as its magnitude with the highest bit set for negative values.
+fn bytify_implementation_sign_magnitude<O: ByteOrder>(arg: &Expr, output: &mut Vec<u8>) -> Result<(), Error> {
+    let (negative, int) = fold_int(arg.clone())?;
+    let suffix = match int.suffix {
+        // The smallest signed width leaving the highest bit for the sign.
+        IntSuffix::None => {
+            [IntSuffix::I8, IntSuffix::I16, IntSuffix::I32, IntSuffix::I64, IntSuffix::I128].iter().copied()
+                .find(|suffix| int_fits_suffix(false, int.value, suffix))
+                .unwrap_or(IntSuffix::I128)
+        },
+        suffix => suffix,
+    };
+    let (bits, signed) = match int_suffix_bits(&suffix) {
+        Some(bits) => bits,
+        None => {
+            return Err(Error::invalid_directive_argument(arg, "expected a sized signed integer"));
+        },
+    };
+    if !signed {
+        return Err(Error::invalid_directive_argument(arg, "expected a signed integer"));
+    }
+    let sign = 1u128 << (bits - 1);
+    if int.value >= sign {
+        return Err(Error::invalid_directive_argument(arg, "magnitude does not fit in the sign-magnitude form"));
+    }
+    let unsigned = match suffix {
+        IntSuffix::I8 => IntSuffix::U8,
+        IntSuffix::I16 => IntSuffix::U16,
+        IntSuffix::I32 => IntSuffix::U32,
+        IntSuffix::I64 => IntSuffix::U64,
+        _ => IntSuffix::U128,
+    };
+    bytify_implementation_int::<O>(false, Int {
+        value: if negative { int.value | sign } else { int.value },
+        suffix: unsigned,
+        ..int
+    }, output)
+}
+
+/// Packs the decimal digits of a non-negative integer two per byte, the most significant digit
+/// first, an odd number of digits is completed with the padding nibble in front.
+fn bytify_implementation_bcd(arg: &Expr, pad: Option<&Expr>, output: &mut Vec<u8>) -> Result<(), Error> {
+    let value = match int_literal(arg) {
+        Some(int) => int.value,
+        None => {
+            return Err(Error::invalid_directive_argument(arg, "expected a non-negative integer literal"));
+        },
+    };
+    let pad = match pad {
+        Some(pad) => match int_literal(pad) {
+            Some(Int { value: value @ 0x0, .. }) |
+            Some(Int { value: value @ 0xF, .. }) => value as u8,
+            _ => {
+                return Err(Error::invalid_directive_argument(pad, "padding nibble must be 0x0 or 0xF"));
+            },
+        },
+        None => 0x0,
+    };
+    let mut digits = value.to_string().into_bytes().into_iter().map(|digit| digit - b'0').collect::<Vec<_>>();
+    if digits.len() % 2 == 1 {
+        digits.insert(0, pad);
+    }
+    output.extend(digits.chunks(2).map(|pair| pair[0] << 4 | pair[1]));
+    Ok(())
+}
+
+/// Writes an integer as unsigned LEB128, seven bits per byte starting with the least significant
+/// ones, the highest bit tells whether more bytes follow, `svarint` zigzags the value first so
+/// small negative values stay short, e.g. `-1` becomes `1` and `1` becomes `2`.
+fn bytify_implementation_varint(arg: &Expr, zigzag: bool, output: &mut Vec<u8>) -> Result<(), Error> {
+    let (negative, int) = fold_int(arg.clone())?;
+    if !int_fits_suffix(negative, int.value, &int.suffix) {
+        return Err(Error::integer_overflow(negative, &int));
+    }
+    let mut value = if zigzag {
+        if matches!(int_suffix_bits(&int.suffix), Some((_, false))) {
+            return Err(Error::invalid_directive_argument(arg, "expected a signed integer"));
+        }
+        match (negative, int.value) {
+            (_, 0) => 0,
+            (false, value) if value <= i128::MAX as u128 => value << 1,
+            (true, value) if value <= 1 << 127 => (value - 1) << 1 | 1,
+            _ => {
+                return Err(Error::invalid_directive_argument(arg, "value does not fit in 128 bits"));
+            },
+        }
+    } else {
+        if negative {
+            return Err(Error::invalid_directive_argument(arg, "expected a non-negative integer, use svarint for signed values"));
+        }
+        int.value
+    };
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            output.push(byte);
+            return Ok(());
+        }
+        output.push(byte | 0x80);
+    }
+}
+
+/// Writes `count` integers starting at `start` and changing by `step` each time, e.g. `ramp(0u8, 4, 2)`.
+fn bytify_implementation_ramp<O: ByteOrder>(start: &Expr, count: &Expr, step: Option<&Expr>, output: &mut Vec<u8>) -> Result<(), Error> {
+    let count = directive_len(count)?;
+    let signed = |expr: &Expr| -> Result<(i128, IntSuffix), Error> {
+        let (negative, int) = fold_int(expr.clone())?;
+        if int.value > i128::MAX as u128 {
+            return Err(Error::invalid_directive_argument(expr, "value is too large"));
+        }
+        Ok((if negative { -(int.value as i128) } else { int.value as i128 }, int.suffix))
+    };
+    let (first, suffix) = signed(start)?;
+    let (step, step_suffix) = match step {
+        Some(step) => signed(step)?,
+        None => (1, IntSuffix::None),
+    };
+    let suffix = match (suffix, step_suffix) {
+        (IntSuffix::None, suffix) | (suffix, IntSuffix::None) => suffix,
+        (suffix, step_suffix) if suffix == step_suffix => suffix,
+        _ => {
+            return Err(Error::invalid_directive_argument(start, "start and step have mismatched suffixes"));
+        },
+    };
+    let last = match count.checked_sub(1) {
+        Some(steps) => (steps as i128).checked_mul(step).and_then(|delta| first.checked_add(delta)),
+        None => Some(first),
+    };
+    let last = match last {
+        Some(last) => last,
+        None => {
+            return Err(Error::invalid_directive_argument(start, "ramp does not fit in 128 bits"));
+        },
+    };
+    // All of the values share the width which fits both ends, unless it is given explicitly.
+    let suffix = if suffix != IntSuffix::None { suffix } else { int_common_suffix(first, last) };
+    let mut value = first;
+    for index in 0 .. count {
+        if index > 0 {
+            value += step;
+        }
+        bytify_implementation_int::<O>(value < 0, Int {
+            span: start.span(),
+            repr: value.to_string(),
+            value: value.unsigned_abs(),
+            suffix,
+        }, output)?;
+    }
+    Ok(())
+}
+
+fn bytify_implementation_call(context: Context, call_expr: ExprCall, output: &mut Vec<u8>) -> Result<(), Error> {
+    let name = call_expr.func.clone().into_token_stream().to_string();
+    let args = call_expr.args.iter().cloned().collect::<Vec<_>>();
+    match (name.as_str(), args.as_slice()) {
+        ("include", [Expr::Lit(ExprLit { lit: Lit::Str(path), .. })]) => {
+            let file = include_path(&path.value());
+            let bytes = fs::read(&file).map_err(|err| {
+                Error::IncludeFailed(path.span(), path.value(), err)
+            })?;
+            let file = fs::canonicalize(&file).unwrap_or(file);
+            track(Tracked::File(file.to_string_lossy().into_owned()));
+            output.extend_from_slice(&bytes);
+        },
+        ("env", [Expr::Lit(ExprLit { lit: Lit::Str(name), .. }), default @ ..]) if default.len() <= 1 => {
+            let default = match default.first() {
+                Some(Expr::Lit(ExprLit { lit: Lit::Str(default), .. })) => Some(default.value()),
+                Some(default) => {
+                    return Err(Error::invalid_directive_argument(default, "expected a string literal"));
+                },
+                None => None,
+            };
+            track(Tracked::Env(name.value()));
+            let value = match (env::var(name.value()), default) {
+                (Ok(value), _) => value,
+                (Err(VarError::NotPresent), Some(default)) => default,
+                (Err(err), _) => {
+                    return Err(Error::EnvVarFailed(name.span(), name.value(), err));
+                },
+            };
+            // The value is written just like a string literal, so the encoding ascriptions apply to it.
+            let string = LitStr::new(&value, name.span());
+            if context.string_endianness.unwrap_or(context.endianness) == Endianness::BE {
+                bytify_implementation_string::<BE>(string, context, output)?;
+            } else {
+                bytify_implementation_string::<LE>(string, context, output)?;
+            }
+        },
+        ("fill", [fill, len]) => {
+            let fill = directive_byte(fill)?;
+            let len = directive_len(len)?;
+            output.resize(output.len() + len, fill);
+        },
+        ("fill_pattern", [pattern, count]) => {
+            let bytes = match pattern {
+                Expr::Array(array_expr) => array_expr.elems.iter().map(directive_byte).collect::<Result<Vec<_>, _>>()?,
+                Expr::Lit(ExprLit { lit: Lit::ByteStr(byte_string), .. }) => byte_string.value(),
+                _ => {
+                    return Err(Error::invalid_directive_argument(pattern, "expected an array of byte integer literals or a byte string"));
+                },
+            };
+            if bytes.is_empty() {
+                return Err(Error::invalid_directive_argument(pattern, "pattern must not be empty"));
+            }
+            let count = directive_len(count)?;
+            for _ in 0 .. count {
+                output.extend_from_slice(&bytes);
+            }
+        },
+        ("mirror", [arg]) | ("mirror_be", [arg]) => {
+            // The same value in both byte orders, little endian first unless the name says otherwise.
+            let (negative, int) = fold_int(arg.clone())?;
+            let int = int_with_width(context, negative, int)?;
+            if name == "mirror_be" {
+                bytify_implementation_int::<BE>(negative, int.clone(), output)?;
+                bytify_implementation_int::<LE>(negative, int, output)?;
+            } else {
+                bytify_implementation_int::<LE>(negative, int.clone(), output)?;
+                bytify_implementation_int::<BE>(negative, int, output)?;
+            }
+        },
+        ("bom_utf8", []) => output.extend_from_slice(&[0xEF, 0xBB, 0xBF]),
+        ("bom_utf16le", []) => output.extend_from_slice(&[0xFF, 0xFE]),
+        ("bom_utf16be", []) => output.extend_from_slice(&[0xFE, 0xFF]),
+        ("bom_utf32le", []) => output.extend_from_slice(&[0xFF, 0xFE, 0x00, 0x00]),
+        ("bom_utf32be", []) => output.extend_from_slice(&[0x00, 0x00, 0xFE, 0xFF]),
+        ("reserved", [arg]) => {
+            let len = directive_len(arg)?;
+            if len == 0 {
+                return Err(Error::invalid_directive_argument(arg, "reserved region must not be empty"));
+            }
+            output.resize(output.len() + len, 0x00);
+        },
+        ("align", [alignment, fill @ ..]) if fill.len() <= 1 => {
+            let fill = match fill.first() {
+                Some(fill) => directive_byte(fill)?,
+                None => 0x00,
+            };
+            let len = directive_len(alignment)?;
+            if !len.is_power_of_two() {
+                return Err(Error::invalid_directive_argument(alignment, "alignment must be a power of two"));
+            }
+            while !output.len().is_multiple_of(len) {
+                output.push(fill);
+            }
+        },
+        ("pad_to", [arg]) => {
+            let len = directive_len(arg)?;
+            if output.len() > len {
+                return Err(Error::invalid_directive_argument(arg, &format!("output is already {} bytes long", output.len())));
+            }
+            output.resize(len, 0x00);
+        },
+        ("guid", [Expr::Lit(ExprLit { lit: Lit::Str(uuid), .. })]) |
+        ("guid_ms", [Expr::Lit(ExprLit { lit: Lit::Str(uuid), .. })]) => {
+            let mut bytes = decode_uuid(&uuid.value()).map_err(|reason| {
+                Error::InvalidEncodedString(uuid.span(), format!("{:?}", uuid.value()), reason)
+            })?;
+            // The Microsoft layout stores the first three fields as little endian integers.
+            if name == "guid_ms" {
+                bytes[0 .. 4].reverse();
+                bytes[4 .. 6].reverse();
+                bytes[6 .. 8].reverse();
+            }
+            output.extend_from_slice(&bytes);
+        },
+        ("ipv4", [Expr::Lit(ExprLit { lit: Lit::Str(string), .. })]) => {
+            let address = string.value().parse::<Ipv4Addr>().map_err(|err| {
+                Error::InvalidEncodedString(string.span(), format!("{:?}", string.value()), err.to_string())
+            })?;
+            output.extend_from_slice(&address.octets());
+        },
+        ("ipv6", [Expr::Lit(ExprLit { lit: Lit::Str(string), .. })]) => {
+            let address = string.value().parse::<Ipv6Addr>().map_err(|err| {
+                Error::InvalidEncodedString(string.span(), format!("{:?}", string.value()), err.to_string())
+            })?;
+            output.extend_from_slice(&address.octets());
+        },
+        (name, [arg]) if fixed_point_format(name).is_some() => {
+            let (fraction, suffix) = fixed_point_format(name).unwrap();
+            if context.endianness == Endianness::BE {
+                bytify_implementation_fixed::<BE>(arg, fraction, suffix, output)?;
+            } else {
+                bytify_implementation_fixed::<LE>(arg, fraction, suffix, output)?;
+            }
+        },
+        ("parse", _) => {
+            let (negative, int) = fold_int(Expr::Call(call_expr))?;
+            let int = int_with_width(context, negative, int)?;
+            if context.endianness == Endianness::BE {
+                bytify_implementation_int::<BE>(negative, int, output)?;
+            } else {
+                bytify_implementation_int::<LE>(negative, int, output)?;
+            }
+        },
+        ("f32_bits", [arg]) | ("f64_bits", [arg]) => {
+            let suffix = if name == "f32_bits" { IntSuffix::U32 } else { IntSuffix::U64 };
+            if context.endianness == Endianness::BE {
+                bytify_implementation_float_bits::<BE>(arg, suffix, output)?;
+            } else {
+                bytify_implementation_float_bits::<LE>(arg, suffix, output)?;
+            }
+        },
+        ("ramp", [start, count, step @ ..]) if step.len() <= 1 => {
+            if context.endianness == Endianness::BE {
+                bytify_implementation_ramp::<BE>(start, count, step.first(), output)?;
+            } else {
+                bytify_implementation_ramp::<LE>(start, count, step.first(), output)?;
+            }
+        },
+        ("sign_magnitude", [arg]) => {
+            if context.endianness == Endianness::BE {
+                bytify_implementation_sign_magnitude::<BE>(arg, output)?;
+            } else {
+                bytify_implementation_sign_magnitude::<LE>(arg, output)?;
+            }
+        },
+        ("bcd", [arg, pad @ ..]) if pad.len() <= 1 => {
+            bytify_implementation_bcd(arg, pad.first(), output)?;
+        },
+        ("varint", [arg]) | ("svarint", [arg]) => {
+            bytify_implementation_varint(arg, name == "svarint", output)?;
+        },
+        ("bitflags", flags) | ("bitflags_msb", flags) => {
+            let msb = name == "bitflags_msb";
+            for chunk in flags.chunks(8) {
+                let mut byte = 0u8;
+                for (index, flag) in chunk.iter().enumerate() {
+                    if directive_bool(flag)? {
+                        byte |= if msb { 0x80 >> index } else { 0x01 << index };
+                    }
+                }
+                output.push(byte);
+            }
+        },
+        _ => {
+            return Err(Error::unsupported_expression(Expr::Call(call_expr)));
+        },
+    }
+    Ok(())
+}
+
+/// A value unknown to the macro, its bytes are taken by the compiler instead of the zeros
+/// reserved in the output.
+#[derive(Debug)]
+struct Splice {
+    offset: usize,
+    len: usize,
+    value: TokenStream2,
+    ty: Type,
+    endianness: Endianness,
+}
+
+fn bytify_implementation_splice(context: Context, value: TokenStream2, ty: Type, output: &mut Vec<u8>, splices: &mut Vec<Splice>) -> Result<(), Error> {
+    let bits = match (int_suffix_from_type(&ty), float_suffix_from_type(&ty)) {
+        (Some(suffix), _) => int_suffix_bits(&suffix).map(|(bits, _)| bits),
+        (_, Some(FloatSuffix::F32)) => Some(32),
+        (_, Some(FloatSuffix::F64)) => Some(64),
+        _ => None,
+    };
+    let bits = match bits {
+        Some(bits) => bits,
+        None => {
+            return Err(Error::InvalidInterpolation(ty.span(), ty.into_token_stream().to_string()));
+        },
+    };
+    let len = bits as usize / 8;
+    splices.push(Splice {
+        offset: output.len(),
+        len,
+        value,
+        ty,
+        endianness: context.endianness,
+    });
+    output.resize(output.len() + len, 0x00);
+    Ok(())
+}
+
+/// Selects the smallest width fitting both values, signed if any of them is negative.
+fn int_common_suffix(lhs: i128, rhs: i128) -> IntSuffix {
+    let candidates = if lhs < 0 || rhs < 0 {
+        [IntSuffix::I8, IntSuffix::I16, IntSuffix::I32, IntSuffix::I64, IntSuffix::I128]
+    } else {
+        [IntSuffix::U8, IntSuffix::U16, IntSuffix::U32, IntSuffix::U64, IntSuffix::U128]
+    };
+    let fits = |suffix: &IntSuffix| {
+        int_fits_suffix(lhs < 0, lhs.unsigned_abs(), suffix) && int_fits_suffix(rhs < 0, rhs.unsigned_abs(), suffix)
+    };
+    candidates.iter().copied().find(fits).unwrap_or(IntSuffix::I128)
+}
+
+/// Writes every value of an ascending range of chars or integers, e.g. `'a' ..= 'z'`.
+fn bytify_implementation_range<O: ByteOrder>(range_expr: ExprRange, context: Context, output: &mut Vec<u8>) -> Result<(), Error> {
+    let span = range_expr.span();
+    let repr = range_expr.clone().into_token_stream().to_string();
+    let invalid = |reason: &str| Error::InvalidRange(span, repr.clone(), reason.to_string());
+    let inclusive = matches!(range_expr.limits, RangeLimits::Closed(_));
+    let (start, end) = match (range_expr.start, range_expr.end) {
+        (Some(start), Some(end)) => (*start, *end),
+        _ => {
+            return Err(invalid("both bounds are required"));
+        },
+    };
+    match (&start, &end) {
+        (Expr::Lit(ExprLit { lit: Lit::Char(start), .. }), Expr::Lit(ExprLit { lit: Lit::Char(end), .. })) => {
+            let (start, end) = (start.value() as u32, end.value() as u32);
+            if start > end {
+                return Err(invalid("the range is descending"));
+            }
+            let end = if inclusive { end + 1 } else { end };
+            // Surrogates are skipped just like the `RangeInclusive<char>` iterator does.
+            for c in (start .. end).filter_map(char::from_u32) {
+                bytify_implementation_str::<O>(c.encode_utf8(&mut [0u8; 4]), span, context.encoding, output)?;
+            }
+        },
+        _ if is_float_expr(&start) || is_float_expr(&end) => {
+            return Err(invalid("bounds must be char or integer literals"));
+        },
+        _ => {
+            let (start_negative, start) = fold_int(start)?;
+            let (end_negative, end) = fold_int(end)?;
+            let suffix = match (start.suffix, end.suffix) {
+                (IntSuffix::None, suffix) | (suffix, IntSuffix::None) => suffix,
+                (start_suffix, end_suffix) if start_suffix == end_suffix => start_suffix,
+                _ => {
+                    return Err(invalid("bounds have mismatched suffixes"));
+                },
+            };
+            let signed = |negative: bool, value: u128| -> Option<i128> {
+                if value > i128::MAX as u128 {
+                    return None;
+                }
+                Some(if negative { -(value as i128) } else { value as i128 })
+            };
+            let (start, end) = match (signed(start_negative, start.value), signed(end_negative, end.value)) {
+                (Some(start), Some(end)) => (start, end),
+                _ => {
+                    return Err(invalid("bounds are too large"));
+                },
+            };
+            if start > end {
+                return Err(invalid("the range is descending"));
+            }
+            // All of the values share the width which fits both bounds, unless it is given explicitly.
+            let suffix = if suffix != IntSuffix::None { suffix } else { int_common_suffix(start, end) };
+            let values = if inclusive { start ..= end } else { start ..= end - 1 };
+            for value in values {
+                bytify_implementation_int::<O>(value < 0, Int {
+                    span,
+                    repr: value.to_string(),
+                    value: value.unsigned_abs(),
+                    suffix,
+                }, output)?;
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Widens an integer without a suffix up to the width set by an ascription, `i16` and alike for
+/// negative integers and `u16` and alike for the rest, a value not fitting that width is an error
+/// rather than silently written wider.
+fn int_with_width(context: Context, negative: bool, int: Int) -> Result<Int, Error> {
+    if int.suffix != IntSuffix::None {
+        return Ok(int);
+    }
+    match (negative, context.signed_width, context.unsigned_width) {
+        (true, Some(suffix), _) | (false, _, Some(suffix)) => {
+            if !int_fits_suffix(negative, int.value, &suffix) {
+                return Err(Error::IntegerOutOfRange(int.span, int.repr, negative));
+            }
+            Ok(Int { suffix, ..int })
+        },
+        (true, None, Some(suffix)) => {
+            Err(Error::negative_unsigned(&Int { suffix, ..int }))
+        },
+        _ => Ok(int),
+    }
+}
+
+/// Rejects f32-suffixed floats losing precision under the `f32_strict` ascription instead of
+/// rounding them, floats without a suffix never lose it anyway.
+fn float_with_strict_narrowing(context: Context, negative: bool, float: Float) -> Result<Float, Error> {
+    if context.strict_f32 && float.suffix == FloatSuffix::F32 && f64::from(float.value as f32) != float.value {
+        return Err(Error::InexactFloat(float.span, float.repr, negative));
+    }
+    Ok(float)
+}
+
+fn bytify_implementation_expr(context: Context, expr: Expr, output: &mut Vec<u8>) -> Result<(), Error> {
+    match expr {
+        Expr::Binary(binary_expr) if matches!(binary_expr.op, BinOp::Mul(_)) && (is_string_expr(&binary_expr.left) || is_string_expr(&binary_expr.right)) => {
+            let (string, len) = if is_string_expr(&binary_expr.left) {
+                (binary_expr.left, binary_expr.right)
+            } else {
+                (binary_expr.right, binary_expr.left)
+            };
+            let len = repeat_len(*len)?;
+            let mut element = Vec::new();
+            bytify_implementation_expr(context, *string, &mut element)?;
+            for _ in 0 .. len {
+                output.extend_from_slice(&element);
+            }
+        },
+        Expr::Binary(binary_expr) => {
+            let (negative, int) = fold_int(Expr::Binary(binary_expr))?;
+            let int = int_with_width(context, negative, int)?;
+            if context.endianness == Endianness::BE {
+                bytify_implementation_int::<BE>(negative, int, output)?;
+            } else {
+                bytify_implementation_int::<LE>(negative, int, output)?;
+            }
+        },
+        Expr::Cast(cast_expr) => {
+            if float_suffix_from_type(&cast_expr.ty).is_some() {
+                let (negative, float) = fold_float(Expr::Cast(cast_expr))?;
+                let float = float_with_strict_narrowing(context, negative, float)?;
+                if context.endianness == Endianness::BE {
+                    bytify_implementation_float::<BE>(negative, float, output)?;
+                } else {
+                    bytify_implementation_float::<LE>(negative, float, output)?;
+                }
+            } else {
+                let (negative, int) = fold_int(Expr::Cast(cast_expr))?;
+                if context.endianness == Endianness::BE {
+                    bytify_implementation_int::<BE>(negative, int, output)?;
+                } else {
+                    bytify_implementation_int::<LE>(negative, int, output)?;
+                }
+            }
+        },
+        Expr::Paren(paren_expr) => {
+            bytify_implementation_expr(context, *paren_expr.expr, output)?;
+        },
+        Expr::Group(group_expr) => {
+            bytify_implementation_expr(context, *group_expr.expr, output)?;
+        },
+        Expr::Array(array_expr) => {
+            for expr in array_expr.elems {
+                bytify_implementation_expr(context, expr, output)?;
+            }
+        },
+        Expr::Tuple(tuple_expr) => {
+            for expr in tuple_expr.elems {
+                bytify_implementation_expr(context, expr, output)?;
+            }
+        },
+        Expr::Repeat(repeat_expr) => {
+            let len = repeat_len(*repeat_expr.len)?;
+            let mut element = Vec::new();
+            bytify_implementation_expr(context, *repeat_expr.expr, &mut element)?;
+            for _ in 0 .. len {
+                output.extend_from_slice(&element);
+            }
+        },
+        Expr::Lit(lit_expr) => {
+            let endianness = match lit_expr.lit {
+                Lit::Str(_) | Lit::Char(_) => context.string_endianness.unwrap_or(context.endianness),
+                _ => context.endianness,
+            };
+            if endianness == Endianness::BE {
+                bytify_implementation_lit::<BE>(lit_expr.lit, context, output)?;
+            } else {
+                bytify_implementation_lit::<LE>(lit_expr.lit, context, output)?;
+            }
+        },
+        Expr::Unary(unary_expr) => {
+            match unary_expr.op {
+                UnOp::Neg(op) => {
+                    match *unary_expr.expr {
+                        Expr::Lit(lit_expr) => {
+                            match lit_expr.lit {
+                                Lit::Int(int) => {
+                                    let int = int_with_width(context, true, Int::from_lit_int(&int)?)?;
+                                    if context.endianness == Endianness::BE {
+                                        bytify_implementation_int::<BE>(true, int, output)?;
+                                    } else {
+                                        bytify_implementation_int::<LE>(true, int, output)?;
+                                    }
+                                },
+                                Lit::Float(float) => {
+                                    let float = float_with_strict_narrowing(context, true, Float::from_lit_float(&float)?)?;
+                                    if context.endianness == Endianness::BE {
+                                        bytify_implementation_float::<BE>(true, float, output)?;
+                                    } else {
+                                        bytify_implementation_float::<LE>(true, float, output)?;
+                                    }
+                                },
+                                lit => {
+                                    return Err(Error::unsupported_lit(lit));
+                                },
+                            }
+                        },
+                        expr if is_float_expr(&expr) => {
+                            // The sign is kept aside and applied last, so `-(0.0 as f32)` is still a negative zero.
+                            let (negative, float) = fold_float(expr)?;
+                            let float = float_with_strict_narrowing(context, !negative, float)?;
+                            if context.endianness == Endianness::BE {
+                                bytify_implementation_float::<BE>(!negative, float, output)?;
+                            } else {
+                                bytify_implementation_float::<LE>(!negative, float, output)?;
+                            }
+                        },
+                        expr => {
+                            let (negative, int) = fold_int(Expr::Unary(ExprUnary {
+                                attrs: Vec::new(),
+                                op: UnOp::Neg(op),
+                                expr: Box::new(expr),
+                            }))?;
+                            let int = int_with_width(context, negative, int)?;
+                            if context.endianness == Endianness::BE {
+                                bytify_implementation_int::<BE>(negative, int, output)?;
+                            } else {
+                                bytify_implementation_int::<LE>(negative, int, output)?;
+                            }
+                        },
+                    }
+                },
+                UnOp::Not(op) => {
+                    let (negative, int) = fold_int(Expr::Unary(ExprUnary {
+                        attrs: Vec::new(),
+                        op: UnOp::Not(op),
+                        expr: unary_expr.expr,
+                    }))?;
+                    let int = int_with_width(context, negative, int)?;
+                    if context.endianness == Endianness::BE {
+                        bytify_implementation_int::<BE>(negative, int, output)?;
+                    } else {
+                        bytify_implementation_int::<LE>(negative, int, output)?;
+                    }
+                },
+                op => {
+                    return Err(Error::unsupported_prefixed_expression(op, *unary_expr.expr));
+                },
+            }
+        },
+        Expr::Call(call_expr) => {
+            bytify_implementation_call(context, call_expr, output)?;
+        },
+        Expr::Range(range_expr) => {
+            let endianness = match range_expr.start.as_deref() {
+                Some(Expr::Lit(ExprLit { lit: Lit::Char(_), .. })) => context.string_endianness.unwrap_or(context.endianness),
+                _ => context.endianness,
+            };
+            if endianness == Endianness::BE {
+                bytify_implementation_range::<BE>(range_expr, context, output)?;
+            } else {
+                bytify_implementation_range::<LE>(range_expr, context, output)?;
+            }
+        },
+        expr => {
+            return Err(Error::unsupported_expression(expr));
+        },
+    }
+    Ok(())
+}
+
+fn bytify_implementation_group(context: Context, name: &str, mut group: Vec<u8>, output: &mut Vec<u8>) {
+    match name {
+        "neg_group" => {
+            // The whole group is a single integer, its least significant byte depends on the endianness.
+            if context.endianness == Endianness::BE {
+                group.reverse();
+            }
+            let mut carry = true;
+            for byte in group.iter_mut() {
+                let (negated, overflow) = (!*byte).overflowing_add(carry as u8);
+                *byte = negated;
+                carry = overflow;
+            }
+            if context.endianness == Endianness::BE {
+                group.reverse();
+            }
+            output.extend_from_slice(&group);
+        },
+        _ => {
+            let digits = if name == "ashex_lower" { b"0123456789abcdef" } else { b"0123456789ABCDEF" };
+            for byte in group {
+                output.push(digits[(byte >> 4) as usize]);
+                output.push(digits[(byte & 0x0F) as usize]);
+            }
+        },
+    }
+}
+
+/// Collects the endianness ascriptions of the elements inside a group, nested ones included.
+fn inner_endianness(context: Context, elements: &[Element], found: &mut Vec<(Ident, Endianness)>) -> Result<(), Error> {
+    for element in elements {
+        match element {
+            Element::Ascription(element, ident) => {
+                let context = context.with_ascription(ident)?;
+                if is_endianness(ident) {
+                    found.push((ident.clone(), context.endianness));
+                }
+                inner_endianness(context, std::slice::from_ref(element), found)?;
+            },
+            Element::Array(elements) | Element::Group(_, elements) => inner_endianness(context, elements, found)?,
+            Element::Repeat(element, _) | Element::Field(_, element, _) => inner_endianness(context, std::slice::from_ref(element), found)?,
+            Element::Expr(_) | Element::Splice(..) => {},
+        }
+    }
+    Ok(())
+}
+
+fn bytify_implementation_element(context: Context, element: Element, output: &mut Vec<u8>, splices: &mut Vec<Splice>) -> Result<(), Error> {
+    match element {
+        Element::Expr(expr) => {
+            bytify_implementation_expr(context, expr, output)?;
+        },
+        Element::Array(elements) => {
+            for element in elements {
+                bytify_implementation_element(context, element, output, splices)?;
+            }
+        },
+        Element::Repeat(element, len) => {
+            let len = repeat_len(len)?;
+            let mut element_output = Vec::new();
+            let mut element_splices = Vec::new();
+            bytify_implementation_element(context, *element, &mut element_output, &mut element_splices)?;
+            for _ in 0 .. len {
+                for splice in &element_splices {
+                    splices.push(Splice {
+                        offset: output.len() + splice.offset,
+                        len: splice.len,
+                        value: splice.value.clone(),
+                        ty: splice.ty.clone(),
+                        endianness: splice.endianness,
+                    });
+                }
+                output.extend_from_slice(&element_output);
+            }
+        },
+        Element::Ascription(element, ident) => {
+            // Blocks and arrays could still mix single bytes with numbers, but a direct ascription is a mistake.
+            if let Element::Expr(Expr::Lit(ExprLit { lit: lit @ (Lit::ByteStr(_) | Lit::Byte(_) | Lit::Bool(_)), .. })) = element.as_ref() {
+                if is_endianness(&ident) {
+                    return Err(Error::MeaninglessEndianness(ident.span(), lit.into_token_stream().to_string()));
+                }
+            }
+            bytify_implementation_element(context.with_ascription(&ident)?, *element, output, splices)?;
+        },
+        Element::Splice(value, ty) => {
+            bytify_implementation_splice(context, value, ty, output, splices)?;
+        },
+        Element::Group(func, elements) => {
+            // The group is negated as a single integer in the byte order its elements are written in.
+            let mut group_context = context;
+            if func == "neg_group" {
+                let mut found = Vec::new();
+                inner_endianness(context, &elements, &mut found)?;
+                if let Some((ident, _)) = found.iter().find(|(_, endianness)| *endianness != found[0].1) {
+                    return Err(Error::InvalidDirectiveArgument(ident.span(), func.to_string(), format!("endianness `{}` of an element inside differs from `{}` of another one", ident, found[0].0)));
+                }
+                if let Some((_, endianness)) = found.first() {
+                    group_context.endianness = *endianness;
+                }
+            }
+            let mut group = Vec::new();
+            let mut group_splices = Vec::new();
+            for element in elements {
+                bytify_implementation_element(context, element, &mut group, &mut group_splices)?;
+            }
+            // The length of interpolated values is known, so these are only moved after the prefix.
+            if let Some((bits, endianness)) = block_prefix(&func.to_string()).or_else(|| tlv_prefix(&func.to_string())) {
+                if group.len() as u128 > int_mask(bits) {
+                    return Err(Error::InvalidDirectiveArgument(func.span(), func.to_string(), format!("value of {} bytes does not fit in its length prefix", group.len())));
+                }
+                write_length_prefix(group.len(), bits, endianness, output)?;
+                for splice in group_splices {
+                    splices.push(Splice {
+                        offset: output.len() + splice.offset,
+                        ..splice
+                    });
+                }
+                output.extend_from_slice(&group);
+                return Ok(());
+            }
+            if !group_splices.is_empty() {
+                return Err(Error::InvalidDirectiveArgument(func.span(), func.to_string(), "bytes of interpolated values are unknown to the macro".to_string()));
+            }
+            bytify_implementation_group(group_context, &func.to_string(), group, output);
+        },
+        Element::Field(func, element, options) => {
+            let mut field = Vec::new();
+            let mut field_splices = Vec::new();
+            bytify_implementation_element(context, *element, &mut field, &mut field_splices)?;
+            bytify_implementation_fixed_field(func, options, field, field_splices, output, splices)?;
+        },
+    }
+    Ok(())
+}
+
+/// Places the bytes of an element into a field of the given `size`, the rest is filled with the
+/// `fill` byte (`0x00` by default) after the bytes for `align: left` (the default) or before them
+/// for `align: right`, bytes which do not fit are an error unless `overflow: truncate` is given.
+fn bytify_implementation_fixed_field(func: Ident, options: Vec<FieldValue>, mut field: Vec<u8>, field_splices: Vec<Splice>, output: &mut Vec<u8>, splices: &mut Vec<Splice>) -> Result<(), Error> {
+    let mut size = None;
+    let mut fill = 0x00;
+    let mut right = false;
+    let mut truncate = false;
+    for option in &options {
+        let name = match &option.member {
+            Member::Named(name) => name.to_string(),
+            Member::Unnamed(_) => String::new(),
+        };
+        let value = option.expr.clone().into_token_stream().to_string();
+        match (name.as_str(), value.as_str()) {
+            ("size", _) => size = Some(directive_len(&option.expr)?),
+            ("fill", _) => fill = directive_byte(&option.expr)?,
+            ("align", "left") => right = false,
+            ("align", "right") => right = true,
+            ("align", _) => {
+                return Err(Error::invalid_directive_argument(&option.expr, "expected left or right"));
+            },
+            ("overflow", "error") => truncate = false,
+            ("overflow", "truncate") => truncate = true,
+            ("overflow", _) => {
+                return Err(Error::invalid_directive_argument(&option.expr, "expected error or truncate"));
+            },
+            _ => {
+                return Err(Error::InvalidDirectiveArgument(option.member.span(), option.member.clone().into_token_stream().to_string(), "expected one of the options size, fill, align or overflow".to_string()));
+            },
+        }
+    }
+    let size = match size {
+        Some(size) => size,
+        None => {
+            return Err(Error::InvalidDirectiveArgument(func.span(), func.to_string(), "size of the field is required".to_string()));
+        },
+    };
+    if field.len() > size {
+        if !truncate {
+            return Err(Error::InvalidDirectiveArgument(func.span(), func.to_string(), format!("{} bytes do not fit in the field of {} bytes", field.len(), size)));
+        }
+        if field_splices.iter().any(|splice| splice.offset + splice.len > size) {
+            return Err(Error::InvalidDirectiveArgument(func.span(), func.to_string(), "bytes of interpolated values could not be truncated".to_string()));
+        }
+        field.truncate(size);
+    }
+    let padding = size - field.len();
+    if right {
+        output.resize(output.len() + padding, fill);
+    }
+    for splice in field_splices {
+        splices.push(Splice {
+            offset: output.len() + splice.offset,
+            ..splice
+        });
+    }
+    output.extend_from_slice(&field);
+    if !right {
+        output.resize(output.len() + padding, fill);
+    }
+    Ok(())
+}
+
+/// Writes the value of a field with the width of its declared type, a suffix of the value
+/// itself must agree with the type.
+fn bytify_implementation_field(context: Context, field: Field, output: &mut Vec<u8>, splices: &mut Vec<Splice>) -> Result<(), Error> {
+    let context = match &field.endianness {
+        Some(ident) => context.with_ascription(ident)?,
+        None => context,
+    };
+    // Constants are left to the compiler just like the interpolated values.
+    if let Expr::Path(path_expr) = field.value {
+        return bytify_implementation_splice(context, path_expr.into_token_stream(), field.ty, output, splices);
+    }
+    if let Some(suffix) = int_suffix_from_type(&field.ty) {
+        let (negative, int) = fold_int(field.value)?;
+        if int.suffix != IntSuffix::None && int.suffix != suffix {
+            return Err(Error::IncompatibleNumberSuffix(int.span, int.repr, negative, format!("{:?}", int.suffix), format!("{:?}", suffix)));
+        }
+        if context.endianness == Endianness::BE {
+            bytify_implementation_int::<BE>(negative, Int { suffix, ..int }, output)?;
+        } else {
+            bytify_implementation_int::<LE>(negative, Int { suffix, ..int }, output)?;
+        }
+    } else if let Some(suffix) = float_suffix_from_type(&field.ty) {
+        let (negative, float) = if is_float_expr(&field.value) {
+            fold_float(field.value)?
+        } else {
+            let (negative, int) = fold_int(field.value)?;
+            (negative, Float {
+                span: int.span,
+                repr: int.repr,
+                value: int.value as f64,
+                suffix: FloatSuffix::None,
+            })
+        };
+        if float.suffix != FloatSuffix::None && float.suffix != suffix {
+            return Err(Error::IncompatibleNumberSuffix(float.span, float.repr, negative, format!("{:?}", float.suffix), format!("{:?}", suffix)));
+        }
+        let float = float_with_strict_narrowing(context, negative, Float { suffix, ..float })?;
+        if context.endianness == Endianness::BE {
+            bytify_implementation_float::<BE>(negative, float, output)?;
+        } else {
+            bytify_implementation_float::<LE>(negative, float, output)?;
+        }
+    } else {
+        return Err(Error::UnsupportedCast(field.ty.span(), field.ty.into_token_stream().to_string()));
+    }
+    Ok(())
+}
+
+fn bytify_struct_implementation(ascription: Option<Ident>, fields: Punctuated<Field, Token![,]>) -> Result<(Vec<u8>, Vec<Splice>), Error> {
+    take_tracked();
+    let context = match ascription {
+        Some(ident) => Context::default().with_ascription(&ident)?,
+        None => Context::default(),
+    };
+    let mut output = Vec::new();
+    let mut splices = Vec::new();
+    let len = fields.len();
+    for (index, field) in fields.into_iter().enumerate() {
+        bytify_implementation_field(context, field, &mut output, &mut splices)
+            .map_err(|err| err.in_element(index, len))?;
+    }
+    Ok((output, splices))
+}
+
+/// Reads the endianness out of the `#[bytify(endian = "be")]` attributes, if any.
+fn derive_endianness(attrs: &[Attribute]) -> Result<Option<Endianness>, Error> {
+    let mut endian: Option<LitStr> = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("bytify")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("endian") {
+                endian = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `endian = \"be\"` or `endian = \"le\"`"))
+            }
+        }).map_err(|err| Error::InvalidAttribute(err.span(), err.to_string()))?;
+    }
+    let endian = match endian {
+        Some(endian) => endian,
+        None => {
+            return Ok(None);
+        },
+    };
+    match endian.parse::<Ident>() {
+        Ok(ident) if is_endianness(&ident) => Ok(Some(Context::default().with_ascription(&ident)?.endianness)),
+        _ => Err(Error::InvalidEndianness(endian.span(), endian.value())),
+    }
+}
+
+/// Writes a field of a numeric type, or an array of these, at the run time.
+fn derive_field_bytes(value: TokenStream2, ty: &Type, endianness: Endianness) -> Result<TokenStream2, Error> {
+    if let Type::Array(array) = ty {
+        let item = derive_field_bytes(quote!(item), &array.elem, endianness)?;
+        return Ok(quote! {
+            for item in (#value).iter() {
+                #item
+            }
+        });
+    }
+    let sized = match (int_suffix_from_type(ty), float_suffix_from_type(ty)) {
+        (Some(suffix), _) => int_suffix_bits(&suffix).is_some(),
+        (_, Some(suffix)) => suffix == FloatSuffix::F32 || suffix == FloatSuffix::F64,
+        _ => false,
+    };
+    if !sized {
+        return Err(Error::InvalidInterpolation(ty.span(), ty.into_token_stream().to_string()));
+    }
+    let bytes = if endianness == Endianness::BE {
+        quote!(to_be_bytes)
+    } else {
+        quote!(to_le_bytes)
+    };
+    Ok(quote! {
+        bytes.extend_from_slice(&(#value).#bytes());
+    })
+}
+
+/// Generates `to_bytes` writing the fields of a struct in order with the widths of their types.
+fn bytify_derive_implementation(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let endianness = derive_endianness(&input.attrs)?.unwrap_or(DEFAULT_ENDIANNESS);
+    let fields = match input.data {
+        Data::Struct(data) => data.fields,
+        _ => {
+            return Err(Error::UnsupportedDeriveInput(input.ident.span(), input.ident.to_string()));
+        },
+    };
+    let mut writes = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        let endianness = derive_endianness(&field.attrs)?.unwrap_or(endianness);
+        writes.push(derive_field_bytes(quote!(self.#member), &field.ty, endianness)?);
+    }
+    let name = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Writes the fields in order, each with the width of its type.
+            pub fn to_bytes(&self) -> ::bytify::__private::Vec<u8> {
+                let mut bytes = ::bytify::__private::Vec::new();
+                #(#writes)*
+                bytes
+            }
+        }
+    })
+}
+
+/// Upper bound of the preallocated output, a bogus repeat count must not abort the expansion.
+const MAX_ESTIMATED_LEN: usize = 1 << 20;
+
+/// Roughly estimates the length of the expression output, only used to preallocate it.
+fn estimate_expr_len(expr: &Expr) -> usize {
+    match expr {
+        Expr::Lit(lit_expr) => {
+            match &lit_expr.lit {
+                Lit::Str(string) => string.value().len(),
+                Lit::ByteStr(byte_string) => byte_string.value().len(),
+                Lit::Char(c) => c.value().len_utf8(),
+                Lit::Int(int) => {
+                    match IntSuffix::from_lit_int(int).ok().as_ref().and_then(int_suffix_bits) {
+                        Some((bits, _)) => bits as usize / 8,
+                        None => 1,
+                    }
+                },
+                Lit::Float(float) => {
+                    match FloatSuffix::from_lit_float(float) {
+                        Ok(FloatSuffix::F16) => 2,
+                        Ok(FloatSuffix::F64) => 8,
+                        _ => 4,
+                    }
+                },
+                _ => 1,
+            }
+        },
+        Expr::Paren(paren_expr) => estimate_expr_len(&paren_expr.expr),
+        Expr::Group(group_expr) => estimate_expr_len(&group_expr.expr),
+        Expr::Unary(unary_expr) => estimate_expr_len(&unary_expr.expr),
+        Expr::Array(array_expr) => array_expr.elems.iter().map(estimate_expr_len).fold(0, usize::saturating_add),
+        Expr::Tuple(tuple_expr) => tuple_expr.elems.iter().map(estimate_expr_len).fold(0, usize::saturating_add),
+        Expr::Repeat(repeat_expr) => {
+            let len = int_literal(&repeat_expr.len).map_or(0, |int| int.value.min(usize::MAX as u128) as usize);
+            estimate_expr_len(&repeat_expr.expr).saturating_mul(len)
+        },
+        _ => 0,
+    }
+}
+
+/// Roughly estimates the length of the element output, only used to preallocate it.
+fn estimate_element_len(element: &Element) -> usize {
+    match element {
+        Element::Expr(expr) => estimate_expr_len(expr),
+        Element::Array(elements) => elements.iter().map(estimate_element_len).fold(0, usize::saturating_add),
+        Element::Repeat(element, len) => {
+            let len = int_literal(len).map_or(0, |int| int.value.min(usize::MAX as u128) as usize);
+            estimate_element_len(element).saturating_mul(len)
+        },
+        Element::Ascription(element, _) => estimate_element_len(element),
+        Element::Splice(_, ty) => {
+            match int_suffix_from_type(ty).as_ref().and_then(int_suffix_bits) {
+                Some((bits, _)) => bits as usize / 8,
+                None => 8,
+            }
+        },
+        Element::Field(_, element, _) => estimate_element_len(element),
+        Element::Group(func, elements) => {
+            let len = elements.iter().map(estimate_element_len).fold(0, usize::saturating_add);
+            match func.to_string().as_str() {
+                "ashex" | "ashex_lower" => len.saturating_mul(2),
+                _ => len,
+            }
+        },
+    }
+}
+
+/// Computes a checksum over the bytes written so far, along with the suffix of the result and
+/// the endianness fixed by the name of the directive, if any.
+///
+/// * `sum8`, `sum16_le` and `sum16_be` are the sums of all bytes truncated to 8 or 16 bits.
+/// * `crc16_ccitt` is CRC-16/CCITT-FALSE: polynomial `0x1021`, init `0xFFFF`, not reflected,
+///   no final xor, `"123456789"` gives `0x29B1`.
+/// * `crc32` is CRC-32/ISO-HDLC as in zlib: reflected polynomial `0xEDB88320`, init and final
+///   xor `0xFFFFFFFF`, `"123456789"` gives `0xCBF43926`.
+fn checksum(name: &str, bytes: &[u8]) -> Option<(u128, IntSuffix, Option<Endianness>)> {
+    let sum = || bytes.iter().fold(0u128, |sum, &byte| sum + byte as u128);
+    match name {
+        "sum8" => Some((sum() & 0xFF, IntSuffix::U8, None)),
+        "sum16_le" => Some((sum() & 0xFFFF, IntSuffix::U16, Some(Endianness::LE))),
+        "sum16_be" => Some((sum() & 0xFFFF, IntSuffix::U16, Some(Endianness::BE))),
+        "crc16_ccitt" => {
+            let mut crc = 0xFFFFu16;
+            for &byte in bytes {
+                crc ^= (byte as u16) << 8;
+                for _ in 0 .. 8 {
+                    crc = if crc & 0x8000 != 0 { crc << 1 ^ 0x1021 } else { crc << 1 };
+                }
+            }
+            Some((crc as u128, IntSuffix::U16, None))
+        },
+        "crc32" => {
+            let mut crc = 0xFFFF_FFFFu32;
+            for &byte in bytes {
+                crc ^= byte as u32;
+                for _ in 0 .. 8 {
+                    crc = if crc & 1 != 0 { crc >> 1 ^ 0xEDB8_8320 } else { crc >> 1 };
+                }
+            }
+            Some((!crc as u128, IntSuffix::U32, None))
+        },
+        _ => None,
+    }
+}
+
+/// Computes a digest over the bytes written so far, which is written as is regardless of the
+/// endianness.
+///
+/// * `sha256` is SHA-256 as in FIPS 180-4, `"abc"` gives `ba7816bf...f20015ad`.
+fn digest(name: &str, bytes: &[u8]) -> Option<Vec<u8>> {
+    match name {
+        "sha256" => Some(Sha256::digest(bytes).to_vec()),
+        _ => None,
+    }
+}
+
+fn is_checksum(name: &str) -> bool {
+    checksum(name, &[]).is_some() || digest(name, &[]).is_some()
+}
+
+/// Unwraps a top level checksum directive, e.g. `crc32()` or `crc16_ccitt(): BE`, applying
+/// the ascriptions around it.
+fn checksum_directive(context: Context, element: &Element) -> Result<Option<(Context, &ExprCall)>, Error> {
+    match element {
+        Element::Expr(Expr::Call(call_expr)) if is_checksum(&call_expr.func.to_token_stream().to_string()) => {
+            Ok(Some((context, call_expr)))
+        },
+        Element::Ascription(element, ident) => {
+            match checksum_directive(context, element)? {
+                Some((context, call_expr)) => Ok(Some((context.with_ascription(ident)?, call_expr))),
+                None => Ok(None),
+            }
+        },
+        _ => Ok(None),
+    }
+}
+
+fn bytify_implementation_checksum(context: Context, call_expr: &ExprCall, output: &mut Vec<u8>, splices: &[Splice]) -> Result<(), Error> {
+    let name = call_expr.func.to_token_stream().to_string();
+    if !call_expr.args.is_empty() {
+        return Err(Error::unsupported_expression(Expr::Call(call_expr.clone())));
+    }
+    if !splices.is_empty() {
+        return Err(Error::InvalidDirectiveArgument(call_expr.span(), name, "checksums could not cover interpolated values".to_string()));
+    }
+    if let Some(digest) = digest(&name, output) {
+        output.extend_from_slice(&digest);
+        return Ok(());
+    }
+    let (value, suffix, endianness) = checksum(&name, output).unwrap();
+    let int = Int {
+        span: call_expr.span(),
+        repr: name,
+        value,
+        suffix,
+    };
+    if endianness.unwrap_or(context.endianness) == Endianness::BE {
+        bytify_implementation_int::<BE>(false, int, output)
+    } else {
+        bytify_implementation_int::<LE>(false, int, output)
+    }
+}
+
+fn bytify_implementation(ascription: Option<Ident>, list: Punctuated<Element, Token![,]>) -> Result<(Vec<u8>, Vec<Splice>), Error> {
+    // Leftovers of an expansion that failed.
+    take_tracked();
+    let context = match ascription {
+        Some(ident) => Context::default().with_ascription(&ident)?,
+        None => Context::default(),
+    };
+    let len = list.iter().map(estimate_element_len).fold(0, usize::saturating_add);
+    let mut output: Vec<u8> = Vec::with_capacity(len.min(MAX_ESTIMATED_LEN));
+    let mut splices = Vec::new();
+    let mut assertions = Vec::new();
+    #[cfg(feature = "trace")]
+    let mut table = String::new();
+    let len = list.len();
+    for (index, element) in list.into_iter().enumerate() {
+        #[cfg(feature = "trace")]
+        let offset = output.len();
+        match element {
+            Element::Expr(Expr::Call(call_expr)) if call_expr.func.to_token_stream().to_string() == "assert_len" => {
+                assertions.push((index, call_expr));
+            },
+            // Checksums cover everything written before them, so they are only known at the top level.
+            element => {
+                match checksum_directive(context, &element).map_err(|err| err.in_element(index, len))? {
+                    Some((context, call_expr)) => bytify_implementation_checksum(context, call_expr, &mut output, &splices),
+                    None => bytify_implementation_element(context, element, &mut output, &mut splices),
+                }.map_err(|err| err.in_element(index, len))?;
+            },
+        }
+        #[cfg(feature = "trace")]
+        table.push_str(&format!("element {}: offset {}, length {}\n", index, offset, output.len() - offset));
+    }
+    // The total length is only known once every element is written.
+    for (index, call_expr) in assertions {
+        let arg = match call_expr.args.first() {
+            Some(arg) if call_expr.args.len() == 1 => arg,
+            _ => {
+                return Err(Error::unsupported_expression(Expr::Call(call_expr)).in_element(index, len));
+            },
+        };
+        let expected = directive_len(arg).map_err(|err| err.in_element(index, len))?;
+        if output.len() != expected {
+            return Err(Error::LengthMismatch(arg.span(), output.len(), expected).in_element(index, len));
+        }
+    }
+    #[cfg(feature = "trace")]
+    track(Tracked::Trace(table));
+    Ok((output, splices))
+}
+
+/// Expands into an expression of the `[u8; N]` type, a byte string literal is much cheaper for
+/// the compiler than an array of separate tokens so it is used unless there are interpolations.
+fn bytify_tokens(output: Vec<u8>, splices: Vec<Splice>) -> TokenStream2 {
+    let tracked = take_tracked();
+    if splices.is_empty() {
+        let output = Literal::byte_string(&output);
+        if tracked.is_empty() {
+            return quote!(*#output);
+        }
+        return quote!(*{ #(#tracked)* #output });
+    }
+    let mut tokens: Vec<TokenStream2> = output.iter().map(|byte| quote!(#byte)).collect();
+    for splice in splices {
+        let value = &splice.value;
+        let ty = &splice.ty;
+        let bytes = if splice.endianness == Endianness::BE {
+            quote!(to_be_bytes)
+        } else {
+            quote!(to_le_bytes)
+        };
+        for index in 0 .. splice.len {
+            tokens[splice.offset + index] = quote!(<#ty>::#bytes(#value)[#index]);
+        }
+    }
+    quote! {
+        {
+            #(#tracked)*
+            [
+                #(#tokens),*
+            ]
+        }
+    }
+}
+
+#[proc_macro]
+pub fn bytify(input: TokenStream) -> TokenStream {
+    let input: TokenStream = unlabel_parse_base(input.into()).into();
+    let input = parse_macro_input!(input as MyMacroInput);
+    match bytify_implementation(input.ascription, input.list) {
+        Ok((output, splices)) => {
+            bytify_tokens(output, splices)
+        },
+        Err(err) => {
+            err.to_compile_error()
+        },
+    }.into()
+}
+
+#[proc_macro]
+pub fn bytify_into(input: TokenStream) -> TokenStream {
+    let input: TokenStream = unlabel_parse_base(input.into()).into();
+    let input = parse_macro_input!(input as MyMacroIntoInput);
+    let target = input.target;
+    match bytify_implementation(input.ascription, input.list) {
+        Ok((output, splices)) => {
+            let output = bytify_tokens(output, splices);
+            quote! {
+                (#target).extend_from_slice(&#output)
+            }
+        },
+        Err(err) => {
+            err.to_compile_error()
+        },
+    }.into()
+}
+
+#[proc_macro]
+pub fn bytify_vec(input: TokenStream) -> TokenStream {
+    let input: TokenStream = unlabel_parse_base(input.into()).into();
+    let input = parse_macro_input!(input as MyMacroInput);
+    match bytify_implementation(input.ascription, input.list) {
+        Ok((output, splices)) => {
+            let output = bytify_tokens(output, splices);
+            quote! {
+                <[u8]>::to_vec(&#output)
+            }
+        },
+        Err(err) => {
+            err.to_compile_error()
+        },
+    }.into()
+}
+
+#[proc_macro]
+pub fn bytify_write(input: TokenStream) -> TokenStream {
+    let input: TokenStream = unlabel_parse_base(input.into()).into();
+    let input = parse_macro_input!(input as MyMacroIntoInput);
+    let target = input.target;
+    match bytify_implementation(input.ascription, input.list) {
+        Ok((output, splices)) => {
+            let output = bytify_tokens(output, splices);
+            quote! {
+                {
+                    use ::std::io::Write as _;
+                    (#target).write_all(&#output)
+                }
+            }
+        },
+        Err(err) => {
+            err.to_compile_error()
+        },
+    }.into()
+}
+
+#[proc_macro]
+pub fn bytify_len(input: TokenStream) -> TokenStream {
+    let input: TokenStream = unlabel_parse_base(input.into()).into();
+    let input = parse_macro_input!(input as MyMacroInput);
+    match bytify_implementation(input.ascription, input.list) {
+        Ok((output, _)) => {
+            let len = output.len();
+            let tracked = take_tracked();
+            quote! {
+                {
+                    #(#tracked)*
+                    #len
+                }
+            }
+        },
+        Err(err) => {
+            err.to_compile_error()
+        },
+    }.into()
+}
+
+#[cfg(feature = "trace")]
+#[proc_macro]
+pub fn bytify_trace(input: TokenStream) -> TokenStream {
+    let input: TokenStream = unlabel_parse_base(input.into()).into();
+    let input = parse_macro_input!(input as MyMacroInput);
+    match bytify_implementation(input.ascription, input.list) {
+        Ok(_) => {
+            let table = take_tracked().into_iter().find_map(|tracked| match tracked {
+                Tracked::Trace(table) => Some(table),
+                _ => None,
+            }).unwrap_or_default();
+            quote! {
+                #table
+            }
+        },
+        Err(err) => {
+            err.to_compile_error()
+        },
+    }.into()
+}
+
+#[proc_macro]
+pub fn bytify_const(input: TokenStream) -> TokenStream {
+    let input: TokenStream = unlabel_parse_base(input.into()).into();
+    let input = parse_macro_input!(input as MyMacroConstInput);
+    let attrs = input.attrs;
+    let vis = input.vis;
+    let name = input.name;
+    match bytify_implementation(input.ascription, input.list) {
+        Ok((output, splices)) => {
+            let len = output.len();
+            let output = bytify_tokens(output, splices);
+            quote! {
+                #(#attrs)*
+                #vis const #name: [u8; #len] = #output;
+            }
+        },
+        Err(err) => {
+            err.to_compile_error()
+        },
+    }.into()
+}
+
+#[proc_macro_derive(Bytify, attributes(bytify))]
+pub fn bytify_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match bytify_derive_implementation(input) {
+        Ok(tokens) => {
+            tokens
+        },
+        Err(err) => {
+            err.to_compile_error()
+        },
+    }.into()
+}
+
+#[proc_macro]
+pub fn bytify_struct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as MyMacroStructInput);
+    match bytify_struct_implementation(input.ascription, input.fields) {
+        Ok((output, splices)) => {
+            bytify_tokens(output, splices)
+        },
+        Err(err) => {
+            err.to_compile_error()
+        },
+    }.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pushes the bytes one by one, counting how many times the vector had to grow.
+    fn reallocations(bytes: &[u8], mut output: Vec<u8>) -> usize {
+        let mut count = 0;
+        for &byte in bytes {
+            let capacity = output.capacity();
+            output.push(byte);
+            if output.capacity() != capacity {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn preallocated_output() {
+        let source = vec!["0xFFu8"; 4096].join(", ");
+        let input: MyMacroInput = syn::parse_str(&source).unwrap();
+        let len = input.list.iter().map(estimate_element_len).fold(0, usize::saturating_add);
+        let (output, _) = bytify_implementation(input.ascription, input.list).unwrap();
+        assert_eq!(output.len(), 4096);
+        assert_eq!(len, 4096);
+        // A vector only grows by changing its capacity, the output kept the preallocated one.
+        assert_eq!(output.capacity(), len);
+        let preallocated = reallocations(&output, Vec::with_capacity(len));
+        let grown = reallocations(&output, Vec::new());
+        println!("reallocations for 4096 elements: {} preallocated, {} grown", preallocated, grown);
+        assert_eq!(preallocated, 0);
+        assert!(grown > 0);
+    }
+
 }
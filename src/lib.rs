@@ -3,18 +3,179 @@
 //! List of supported literals:
 //!
 //! * UTF-8 characters (`'?'`) as well as UTF-8 strings (`"こんにちは世界"`).
-//! * Integers, also negative and size-suffixed (`-99u32`).
+//! * Byte strings (`b"\xFF\x00"`), written as is, embedded zeros included.
+//! * Byte characters (`b'A'`), written as a single byte.
+//! * Booleans (`true`, `false`), written as a single `0x01` or `0x00` byte.
+//! * Integers up to 128 bits, also negative and size-suffixed (`-99u32`).
 //!     * Non-suffixed integers are written in a form as small as possible.
+//!     * Non-suffixed negative integers could be given a wider minimal width using the `i16`, `i32`,
+//!       `i64` or `i128` "ascription", e.g. `-1: i32` is written as four bytes instead of one. A value
+//!       not fitting that width is an error, e.g. `-40000: i16`.
+//!     * Non-suffixed non-negative integers are written with the width of the `u8`, `u16` or `u32`
+//!       "ascription", e.g. `5: u16` is written as two bytes. A value not fitting that width or
+//!       a negative one is an error.
 //!     * The little endian is used as a default endianness but could be changed build-wise by
 //!       enabling `default-big-endian` feature.
 //!     * It is possible to set endianness for a single literal using the "ascription"
 //!       syntax, e.g. `9000: LE` or `0x8u32: be`.
+//!     * The colon of an endianness ascription could be omitted, e.g. `0x1234u16 be`.
+//!     * The spelled out `big` and `little` are available as aliases for `BE` and `LE`.
+//!     * The native endianness of the machine expanding the macro is available as `NE`/`ne`.
+//!     * The network byte order is available as `NET`/`net`, an alias for `BE`.
 //! * Floats, also negative and size-suffixed (`-3.1415926f64`).
-//!     * Non-suffixed floats are written in a form as small as possible.
+//!     * Non-suffixed floats are always written as `f32`, a value not exactly representable as `f32`
+//!       is an error and needs a suffix, e.g. `0.1f64` or `1e40f64`.
+//!     * Half precision floats are available using the `f16` suffix (`1.5f16`), a value out of its
+//!       range is an error.
+//!     * Narrowing to `f32` using the suffix rounds silently, the `f32_strict` "ascription" makes it
+//!       an error instead, e.g. `0.1f32: f32_strict`.
 //!     * The little endian is used as a default endianness but could be changed build-wise by
 //!       enabling `default-big-endian` feature.
 //!     * It is possible to set endianness for a single literal using the "ascription"
-//!       syntax, e.g. `0.2: LE` or `-15.92f64: be`.
+//!       syntax, e.g. `0.2f32: LE` or `-15.92f64: be`. The suffix is needed since `0.2` is not
+//!       exactly representable as `f32`, which a non-suffixed float must be.
+//!     * The native endianness of the machine expanding the macro is available as `NE`/`ne`.
+//!     * The network byte order is available as `NET`/`net`, an alias for `BE`.
+//! * Repeated elements using the array syntax (`[0u8; 16]` or `[0xFACEu16: BE; 4]`).
+//! * Nested arrays of any of the above (`[0x01, 0x02: BE]`), flattened into the output. An ascription
+//!   of the whole array applies to every element inside unless overridden (`[1u16, 2u16]: BE`).
+//! * Arithmetic and bitwise expressions over integers (`0x10 + 0x20` or `(1 << 3 | 1) as u8`),
+//!   evaluated beforehand.
+//!     * Suffixed operands must agree with each other, an overflow of their type is an error.
+//!     * Casts truncate the value just like `as` does, e.g. `0x1FF as u8` is `0xFF`, while a literal
+//!       overflowing its own suffix, e.g. `0x1FFu8`, is still an error.
+//!     * Widening casts extend the value by the signedness of its source type, just like `as` does,
+//!       e.g. `-1i8 as u32` is `0xFFFFFFFF` while `0xFFu8 as i32` is `0x000000FF`.
+//! * Casts between numeric types selecting the width of the value (`0x01 as u32: BE` or `1 as f32`).
+//! * UTF-16 characters and strings using the "ascription" syntax (`"héllo": utf16le` or `'é': utf16be`).
+//! * UTF-32 characters and strings using the "ascription" syntax (`"😀": utf32le` or `'é': utf32be`).
+//! * NUL-terminated C strings using the "ascription" syntax (`"name": cstr` or `"name": utf16le: cstr`).
+//!     * Wide C strings of Windows (`LPCWSTR`) are available as `"Path": wcstr`, a shorthand for `utf16le: cstr`,
+//!       and `wcstr_be`, a shorthand for `utf16be: cstr`.
+//! * Length-prefixed strings using the "ascription" syntax (`"hello": len_u8` or `"hello": len_u16_be`).
+//!     * Available prefixes are `len_u8`, `len_u16_le`, `len_u16_be`, `len_u32_le` and `len_u32_be`.
+//!     * The length is counted in bytes of the encoded string, a length not fitting the prefix is an error.
+//!     * Adding `_chars` to a prefix counts Unicode scalar values instead, e.g. `"héllo": len_u16_be_chars`
+//!       is prefixed with 5 while `len_u16_be` or `len_u16_be_bytes` is prefixed with 6.
+//! * Hex-decoded strings using the "ascription" syntax (`"DEAD BEEF 00": hex`), whitespace is ignored.
+//! * Base64-decoded strings using the "ascription" syntax (`"SGk=": base64` or `"-_8": base64url`).
+//! * Contents of a file using the `include("logo.bin")` directive, the path is relative to the
+//!   file invoking the macro just like with `include_bytes!`,
+//!   which the expansion also uses on the file so that the crate is rebuilt when it changes.
+//! * Zero padding up to a power of two alignment using the `align(4)` directive, the padding byte
+//!   could be changed as well, e.g. `align(16, 0xFF)`.
+//! * Byte fills using the `fill(0x00; 8)` directive, writing the given byte the given number of times.
+//!     * Longer patterns are repeated using `fill_pattern([0xDE, 0xAD]; 3)`, the pattern is either an array of bytes
+//!       or a byte string and the count is the number of times the whole pattern is written.
+//! * Reserved regions using the `reserved(16)` directive, writing the given positive number of zeros.
+//! * Zero padding up to an absolute length using the `pad_to(32)` directive, an output already longer
+//!   than that is an error.
+//! * Scoped blocks applying the "ascription" to every element inside (`be { 1u16, 2u32 }` or
+//!   `utf16le { "a", "b" }`), individual elements could still override it.
+//! * Interpolated values of a numeric type using the `#VERSION: u16 BE` or `#(VERSION + 1): u16` syntax,
+//!   the endianness is optional. The values are evaluated by the compiler, so the output is still
+//!   usable in a `const` as long as the interpolated expressions are const-evaluable.
+//! * Characters written as a single code point of a fixed width using the "ascription" syntax
+//!   (`'A': u16 LE` or `'é': u8`), strings are written char by char the same way. A code point
+//!   not fitting the width is an error.
+//! * Tuples grouping several elements (`(1u8, 2u16: BE, "x")`), flattened into the output just like arrays.
+//! * Length assertions using the `assert_len(20)` directive, the total length of the output must
+//!   match it exactly wherever the directive appears among the top level elements.
+//! * Repeated strings using the multiplication syntax (`"ab" * 3` or `b"\x00" * 4`).
+//! * A default "ascription" of the whole invocation using the `@be;` prefix (`bytify!(@be; 1u16, 2u16)`
+//!   or `bytify_into!(buffer, @net; 80u16)`), overriding the `default-big-endian` feature for that
+//!   invocation only. Individual elements could still override it.
+//! * Bit flags packed into bytes using the `bitflags(true, false, true)` directive, the first flag is
+//!   the least significant bit. Use `bitflags_msb` to start from the most significant one, every
+//!   8 flags take a byte.
+//! * GUIDs written from their canonical form using the `guid("550e8400-e29b-41d4-a716-446655440000")`
+//!   directive in the RFC 4122 big endian layout, or `guid_ms(...)` in the Microsoft layout with the
+//!   first three fields in little endian.
+//! * IP addresses in the network byte order using the `ipv4("192.168.0.1")` and `ipv6("::1")` directives.
+//! * Signed fixed point numbers using the `fixed_q8_8(1.5)` or `fixed_q16_16(-0.25)` directives, any
+//!   `fixed_qM_N` with a total width of 8, 16, 32 or 64 bits is available. The value is rounded to
+//!   the nearest step and written with the selected endianness, a value out of range is an error.
+//! * Ascending ranges of chars or integers (`'a'..='z'` or `0u8..16`), writing every value in order.
+//!   The values of a range without suffixes share the smallest width fitting both bounds.
+//! * The default encoding of strings and characters could be changed build-wise to UTF-16 by enabling
+//!   `default-utf16le` or `default-utf16be` feature, the numbers keep their default endianness. The
+//!   `utf8` ascription restores UTF-8 for a single element, an explicit endianness ascription applies
+//!   to the strings as well. Should both features be enabled, e.g. by different dependents,
+//!   `default-utf16le` takes precedence.
+//! * Signed integers in the sign-magnitude form using the `sign_magnitude(-1i8)` directive, the highest
+//!   bit holds the sign and the rest hold the magnitude, so `-1i8` gives `0x81` instead of `0xFF`.
+//!   Non-suffixed values take the smallest signed width fitting the magnitude.
+//! * Binary-coded decimal numbers using the `bcd(1234)` directive, packing two digits per byte with the
+//!   most significant one first. An odd number of digits is padded in front with a zero nibble or
+//!   with the one given, e.g. `bcd(123, 0xF)` gives `[0xF1, 0x23]`.
+//! * Two's complements of element groups using the `neg_group(0x0001u16, 0x02u8)` directive, the bytes
+//!   of the elements inside are negated as a single integer of their total width. The endianness of
+//!   the directive itself selects the least significant byte, e.g. `neg_group(0x0001u16): BE` gives
+//!   `[0xFF, 0xFF]`, unless the elements inside are ascribed with one, e.g. `neg_group(0x0002u16: BE)`
+//!   gives `[0xFF, 0xFE]` as well. Elements ascribed with different ones are an error, so are
+//!   interpolated values, which could not be negated.
+//! * Checksums of everything written before them using the `sum8()`, `sum16_le()`, `sum16_be()`,
+//!   `crc16_ccitt()` and `crc32()` directives, only available among the top level elements. The CRCs
+//!   are written with the selected endianness, e.g. `crc16_ccitt(): BE`.
+//!     * `sum8` and `sum16` are the sums of all bytes truncated to 8 or 16 bits.
+//!     * `crc16_ccitt` is CRC-16/CCITT-FALSE: polynomial `0x1021`, init `0xFFFF`, not reflected, no final xor.
+//!     * `crc32` is CRC-32 as in zlib: reflected polynomial `0xEDB88320`, init and final xor `0xFFFFFFFF`.
+//!     * `sha256()` appends the 32 bytes of the SHA-256 digest as is, whatever the endianness. The digest
+//!       covers everything before the directive in the same invocation and is computed while expanding.
+//! * Exact float bit patterns using the `f32_bits(0x3F800000)` and `f64_bits(...)` directives, written
+//!   with the selected endianness just like the float itself. A suffix of the pattern must be `u32` or
+//!   `u64` respectively, a pattern wider than the float is an error.
+//! * ASCII-only strings and characters using the "ascription" syntax (`"AT+CMGS": ascii`), written as is.
+//!   The first non-ASCII character is an error.
+//! * Latin-1 (ISO-8859-1) strings using the "ascription" syntax (`"café": latin1`), writing every character
+//!   as a single byte. A character above `U+00FF` is an error, the same as for `u8`.
+//! * Unicode strings starting with a byte order mark using the "ascription" syntax (`"text": utf8_bom`,
+//!   `"text": utf16le_bom` or `"text": utf16be_bom`), the mark is counted by length prefixes as well.
+//!     * The marks alone are written by the `bom_utf8()`, `bom_utf16le()`, `bom_utf16be()`, `bom_utf32le()`
+//!       and `bom_utf32be()` directives, whatever the endianness, e.g. to precede text included from a file.
+//! * Environment variables read when the macro is expanded using the `env("VERSION")` directive, written
+//!   just like a string literal, e.g. `env("VERSION"): cstr`. A missing variable is an error unless a
+//!   default is given, e.g. `env("VERSION", "0.0.0")`. The expansion also refers to the variable with
+//!   `option_env!` so that cargo rebuilds the crate when it changes, a build system that does not read
+//!   the dependency info of rustc has to be told to rebuild on its own.
+//! * Arithmetic sequences of integers using the `ramp(0u8, 16, 2)` directive, writing the given number
+//!   of values from the start changing by the optional step, which is 1 by default. The values of a
+//!   sequence without suffixes share the smallest width fitting both ends, an overflow is an error.
+//! * Integers parsed from strings using the `parse("FF", 16)` or `parse("FF", base: 16)` directive,
+//!   the base is 10 by default, e.g. `parse("255", base: 10): u16 BE` gives `[0x00, 0xFF]`.
+//!   The value is just like an integer literal without a suffix, so it could be used in arithmetic or
+//!   casts, e.g. `parse("255") as u16: BE`. Invalid digits or a value above 128 bits are an error.
+//! * ASCII hex representations of element groups using the `ashex(0xDEu8, 0xADu8)` directive, writing
+//!   `"DEAD"` instead of the bytes themselves. Use `ashex_lower` for lowercase digits, interpolated
+//!   values could not be represented.
+//! * Enum discriminants using the `discriminant(Kind::A as u8)` directive, written with the width of the
+//!   cast type. The macro could not evaluate the discriminant itself, so it is interpolated just like
+//!   `#(Kind::A as u8): u8` and the explicit cast of a variant path is required.
+//! * Length-delimited blocks (`block_u16_be { 0x01u8, "hi" }`) prefix the bytes of their elements with their length,
+//!   using the same widths as the length-prefixed strings (`block_u8`, `block_u16_le`, `block_u32_be`, ...).
+//! * Type-length-value records (`tlv(type: 0x01u8, { 0x0A0Bu16: BE, "ok" })`) write the type, the length of the value
+//!   and its bytes, the length is a single byte unless named like a block (`tlv_u16_be(type: 0x01u8, { ... })`).
+//! * Variable-length integers (`varint(300u32)` writes `[0xAC, 0x02]`) in the unsigned LEB128 encoding of protobuf,
+//!   `svarint(-1)` zigzags signed values first, the encoding is the same regardless of the endianness.
+//! * Fixed-size fields (`field("AB", size: 8, fill: 0x20, align: right)`) pad an element with the fill byte, `0x00`
+//!   by default, after the bytes for `align: left`, the default, or before them for `align: right`.
+//!     * Bytes which do not fit are an error unless `overflow: truncate` is given, keeping the leading ones.
+//! * Values in both byte orders using the `mirror(0x1234u16)` directive, writing the integer as little endian
+//!   and then as big endian, `[0x34, 0x12, 0x12, 0x34]`, while `mirror_be(...)` writes big endian first.
+//!
+//! The endianness is honored by integers, floats, fixed point numbers and interpolated values,
+//! it also sets the byte order of UTF-16 and UTF-32 code units and of length prefixes. Everything
+//! else is either a sequence of single bytes or has a layout of its own, e.g. GUIDs and IP addresses.
+//! A meaningless endianness ascription directly on a byte string, a byte or a boolean is an error,
+//! so is a second endianness ascription of the same element (`0x1234u16: BE: LE`).
+//!
+//! The resulting array is a constant expression, so it could be used to initialize a `const` or
+//! a `static`, interpolated values included.
+//!
+//! Enabling the `trace` feature makes the macros record the offset and the length of every top
+//! level element in a hidden `const` of the expansion, which helps to check a layout against its
+//! specification, e.g. with `cargo expand`. The `bytify_trace!` macro returns the same table as
+//! a string. The output itself stays the same.
 //!
 //! # Examples
 //!
@@ -35,24 +196,203 @@
 //!     ][..]);
 //! }
 //! ```
+//!
+//! # `no_std`
+//!
+//! The expansion of the macros never refers to `std`, so these are usable in `no_std` crates
+//! as well. [`bytify_into`] only relies on the `extend_from_slice` method, so it works fine with
+//! `alloc::vec::Vec`, and [`bytify_vec`] only needs `alloc` for `<[u8]>::to_vec`. The methods
+//! derived by [`Bytify`] return an `alloc::vec::Vec` as well. These two are behind the `alloc`
+//! feature, which is enabled by default, without it the crate does not link `alloc` at all.
+//!
+//! ```
+//! extern crate alloc;
+//!
+//! use bytify::{bytify, bytify_vec};
+//!
+//! const MAGIC: [u8; 4] = bytify!(0xCAFEBABEu32: BE);
+//!
+//! fn main() {
+//!     let mut buffer = bytify_vec!(0xCAFEBABEu32: BE);
+//!     assert_eq!(buffer, MAGIC);
+//!     buffer.extend_from_slice(&bytify!(1u16: BE));
+//!     assert_eq!(buffer, [0xCA, 0xFE, 0xBA, 0xBE, 0x00, 0x01]);
+//! }
+//! ```
+//!
+//! [`bytify_into`]: macro.bytify_into.html
+//! [`bytify_vec`]: macro.bytify_vec.html
+//! [`Bytify`]: derive.Bytify.html
+
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 /// Converts a sequence of arbitrary literal values into a single byte array at the compile time.
 ///
 /// List of supported literals:
 ///
 /// * UTF-8 characters (`'?'`) as well as UTF-8 strings (`"こんにちは世界"`).
-/// * Integers, also negative and size-suffixed (`-99u32`).
+/// * Byte strings (`b"\xFF\x00"`), written as is, embedded zeros included.
+/// * Byte characters (`b'A'`), written as a single byte.
+/// * Booleans (`true`, `false`), written as a single `0x01` or `0x00` byte.
+/// * Integers up to 128 bits, also negative and size-suffixed (`-99u32`).
 ///     * Non-suffixed integers are written in a form as small as possible.
+///     * Non-suffixed negative integers could be given a wider minimal width using the `i16`, `i32`,
+///       `i64` or `i128` "ascription", e.g. `-1: i32` is written as four bytes instead of one. A value
+///       not fitting that width is an error, e.g. `-40000: i16`.
+///     * Non-suffixed non-negative integers are written with the width of the `u8`, `u16` or `u32`
+///       "ascription", e.g. `5: u16` is written as two bytes. A value not fitting that width or
+///       a negative one is an error.
 ///     * The little endian is used as a default endianness but could be changed build-wise by
 ///       enabling `default-big-endian` feature.
 ///     * It is possible to set endianness for a single literal using the "ascription"
 ///       syntax, e.g. `9000: LE` or `0x8u32: be`.
+///     * The colon of an endianness ascription could be omitted, e.g. `0x1234u16 be`.
+///     * The spelled out `big` and `little` are available as aliases for `BE` and `LE`.
+///     * The native endianness of the machine expanding the macro is available as `NE`/`ne`.
+///     * The network byte order is available as `NET`/`net`, an alias for `BE`.
 /// * Floats, also negative and size-suffixed (`-3.1415926f64`).
-///     * Non-suffixed floats are written in a form as small as possible.
+///     * Non-suffixed floats are always written as `f32`, a value not exactly representable as `f32`
+///       is an error and needs a suffix, e.g. `0.1f64` or `1e40f64`.
+///     * Half precision floats are available using the `f16` suffix (`1.5f16`), a value out of its
+///       range is an error.
+///     * Narrowing to `f32` using the suffix rounds silently, the `f32_strict` "ascription" makes it
+///       an error instead, e.g. `0.1f32: f32_strict`.
 ///     * The little endian is used as a default endianness but could be changed build-wise by
 ///       enabling `default-big-endian` feature.
 ///     * It is possible to set endianness for a single literal using the "ascription"
-///       syntax, e.g. `0.2: LE` or `-15.92f64: be`.
+///       syntax, e.g. `0.2f32: LE` or `-15.92f64: be`. The suffix is needed since `0.2` is not
+///       exactly representable as `f32`, which a non-suffixed float must be.
+///     * The native endianness of the machine expanding the macro is available as `NE`/`ne`.
+///     * The network byte order is available as `NET`/`net`, an alias for `BE`.
+/// * Repeated elements using the array syntax (`[0u8; 16]` or `[0xFACEu16: BE; 4]`).
+/// * Nested arrays of any of the above (`[0x01, 0x02: BE]`), flattened into the output. An ascription
+///   of the whole array applies to every element inside unless overridden (`[1u16, 2u16]: BE`).
+/// * Arithmetic and bitwise expressions over integers (`0x10 + 0x20` or `(1 << 3 | 1) as u8`),
+///   evaluated beforehand.
+///     * Suffixed operands must agree with each other, an overflow of their type is an error.
+///     * Casts truncate the value just like `as` does, e.g. `0x1FF as u8` is `0xFF`, while a literal
+///       overflowing its own suffix, e.g. `0x1FFu8`, is still an error.
+///     * Widening casts extend the value by the signedness of its source type, just like `as` does,
+///       e.g. `-1i8 as u32` is `0xFFFFFFFF` while `0xFFu8 as i32` is `0x000000FF`.
+/// * Casts between numeric types selecting the width of the value (`0x01 as u32: BE` or `1 as f32`).
+/// * UTF-16 characters and strings using the "ascription" syntax (`"héllo": utf16le` or `'é': utf16be`).
+/// * UTF-32 characters and strings using the "ascription" syntax (`"😀": utf32le` or `'é': utf32be`).
+/// * NUL-terminated C strings using the "ascription" syntax (`"name": cstr` or `"name": utf16le: cstr`).
+///     * Wide C strings of Windows (`LPCWSTR`) are available as `"Path": wcstr`, a shorthand for `utf16le: cstr`,
+///       and `wcstr_be`, a shorthand for `utf16be: cstr`.
+/// * Length-prefixed strings using the "ascription" syntax (`"hello": len_u8` or `"hello": len_u16_be`).
+///     * Available prefixes are `len_u8`, `len_u16_le`, `len_u16_be`, `len_u32_le` and `len_u32_be`.
+///     * The length is counted in bytes of the encoded string, a length not fitting the prefix is an error.
+///     * Adding `_chars` to a prefix counts Unicode scalar values instead, e.g. `"héllo": len_u16_be_chars`
+///       is prefixed with 5 while `len_u16_be` or `len_u16_be_bytes` is prefixed with 6.
+/// * Hex-decoded strings using the "ascription" syntax (`"DEAD BEEF 00": hex`), whitespace is ignored.
+/// * Base64-decoded strings using the "ascription" syntax (`"SGk=": base64` or `"-_8": base64url`).
+/// * Contents of a file using the `include("logo.bin")` directive, the path is relative to the
+///   file invoking the macro just like with `include_bytes!`,
+///   which the expansion also uses on the file so that the crate is rebuilt when it changes.
+/// * Zero padding up to a power of two alignment using the `align(4)` directive, the padding byte
+///   could be changed as well, e.g. `align(16, 0xFF)`.
+/// * Byte fills using the `fill(0x00; 8)` directive, writing the given byte the given number of times.
+///     * Longer patterns are repeated using `fill_pattern([0xDE, 0xAD]; 3)`, the pattern is either an array of bytes
+///       or a byte string and the count is the number of times the whole pattern is written.
+/// * Reserved regions using the `reserved(16)` directive, writing the given positive number of zeros.
+/// * Zero padding up to an absolute length using the `pad_to(32)` directive, an output already longer
+///   than that is an error.
+/// * Scoped blocks applying the "ascription" to every element inside (`be { 1u16, 2u32 }` or
+///   `utf16le { "a", "b" }`), individual elements could still override it.
+/// * Interpolated values of a numeric type using the `#VERSION: u16 BE` or `#(VERSION + 1): u16` syntax,
+///   the endianness is optional. The values are evaluated by the compiler, so the output is still
+///   usable in a `const` as long as the interpolated expressions are const-evaluable.
+/// * Characters written as a single code point of a fixed width using the "ascription" syntax
+///   (`'A': u16 LE` or `'é': u8`), strings are written char by char the same way. A code point
+///   not fitting the width is an error.
+/// * Tuples grouping several elements (`(1u8, 2u16: BE, "x")`), flattened into the output just like arrays.
+/// * Length assertions using the `assert_len(20)` directive, the total length of the output must
+///   match it exactly wherever the directive appears among the top level elements.
+/// * Repeated strings using the multiplication syntax (`"ab" * 3` or `b"\x00" * 4`).
+/// * A default "ascription" of the whole invocation using the `@be;` prefix (`bytify!(@be; 1u16, 2u16)`
+///   or `bytify_into!(buffer, @net; 80u16)`), overriding the `default-big-endian` feature for that
+///   invocation only. Individual elements could still override it.
+/// * Bit flags packed into bytes using the `bitflags(true, false, true)` directive, the first flag is
+///   the least significant bit. Use `bitflags_msb` to start from the most significant one, every
+///   8 flags take a byte.
+/// * GUIDs written from their canonical form using the `guid("550e8400-e29b-41d4-a716-446655440000")`
+///   directive in the RFC 4122 big endian layout, or `guid_ms(...)` in the Microsoft layout with the
+///   first three fields in little endian.
+/// * IP addresses in the network byte order using the `ipv4("192.168.0.1")` and `ipv6("::1")` directives.
+/// * Signed fixed point numbers using the `fixed_q8_8(1.5)` or `fixed_q16_16(-0.25)` directives, any
+///   `fixed_qM_N` with a total width of 8, 16, 32 or 64 bits is available. The value is rounded to
+///   the nearest step and written with the selected endianness, a value out of range is an error.
+/// * Ascending ranges of chars or integers (`'a'..='z'` or `0u8..16`), writing every value in order.
+///   The values of a range without suffixes share the smallest width fitting both bounds.
+/// * The default encoding of strings and characters could be changed build-wise to UTF-16 by enabling
+///   `default-utf16le` or `default-utf16be` feature, the numbers keep their default endianness. The
+///   `utf8` ascription restores UTF-8 for a single element, an explicit endianness ascription applies
+///   to the strings as well. Should both features be enabled, e.g. by different dependents,
+///   `default-utf16le` takes precedence.
+/// * Signed integers in the sign-magnitude form using the `sign_magnitude(-1i8)` directive, the highest
+///   bit holds the sign and the rest hold the magnitude, so `-1i8` gives `0x81` instead of `0xFF`.
+///   Non-suffixed values take the smallest signed width fitting the magnitude.
+/// * Binary-coded decimal numbers using the `bcd(1234)` directive, packing two digits per byte with the
+///   most significant one first. An odd number of digits is padded in front with a zero nibble or
+///   with the one given, e.g. `bcd(123, 0xF)` gives `[0xF1, 0x23]`.
+/// * Two's complements of element groups using the `neg_group(0x0001u16, 0x02u8)` directive, the bytes
+///   of the elements inside are negated as a single integer of their total width. The endianness of
+///   the directive itself selects the least significant byte, e.g. `neg_group(0x0001u16): BE` gives
+///   `[0xFF, 0xFF]`, unless the elements inside are ascribed with one, e.g. `neg_group(0x0002u16: BE)`
+///   gives `[0xFF, 0xFE]` as well. Elements ascribed with different ones are an error, so are
+///   interpolated values, which could not be negated.
+/// * Checksums of everything written before them using the `sum8()`, `sum16_le()`, `sum16_be()`,
+///   `crc16_ccitt()` and `crc32()` directives, only available among the top level elements. The CRCs
+///   are written with the selected endianness, e.g. `crc16_ccitt(): BE`.
+///     * `sum8` and `sum16` are the sums of all bytes truncated to 8 or 16 bits.
+///     * `crc16_ccitt` is CRC-16/CCITT-FALSE: polynomial `0x1021`, init `0xFFFF`, not reflected, no final xor.
+///     * `crc32` is CRC-32 as in zlib: reflected polynomial `0xEDB88320`, init and final xor `0xFFFFFFFF`.
+///     * `sha256()` appends the 32 bytes of the SHA-256 digest as is, whatever the endianness. The digest
+///       covers everything before the directive in the same invocation and is computed while expanding.
+/// * Exact float bit patterns using the `f32_bits(0x3F800000)` and `f64_bits(...)` directives, written
+///   with the selected endianness just like the float itself. A suffix of the pattern must be `u32` or
+///   `u64` respectively, a pattern wider than the float is an error.
+/// * ASCII-only strings and characters using the "ascription" syntax (`"AT+CMGS": ascii`), written as is.
+///   The first non-ASCII character is an error.
+/// * Latin-1 (ISO-8859-1) strings using the "ascription" syntax (`"café": latin1`), writing every character
+///   as a single byte. A character above `U+00FF` is an error, the same as for `u8`.
+/// * Unicode strings starting with a byte order mark using the "ascription" syntax (`"text": utf8_bom`,
+///   `"text": utf16le_bom` or `"text": utf16be_bom`), the mark is counted by length prefixes as well.
+///     * The marks alone are written by the `bom_utf8()`, `bom_utf16le()`, `bom_utf16be()`, `bom_utf32le()`
+///       and `bom_utf32be()` directives, whatever the endianness, e.g. to precede text included from a file.
+/// * Environment variables read when the macro is expanded using the `env("VERSION")` directive, written
+///   just like a string literal, e.g. `env("VERSION"): cstr`. A missing variable is an error unless a
+///   default is given, e.g. `env("VERSION", "0.0.0")`. The expansion also refers to the variable with
+///   `option_env!` so that cargo rebuilds the crate when it changes, a build system that does not read
+///   the dependency info of rustc has to be told to rebuild on its own.
+/// * Arithmetic sequences of integers using the `ramp(0u8, 16, 2)` directive, writing the given number
+///   of values from the start changing by the optional step, which is 1 by default. The values of a
+///   sequence without suffixes share the smallest width fitting both ends, an overflow is an error.
+/// * Integers parsed from strings using the `parse("FF", 16)` or `parse("FF", base: 16)` directive,
+///   the base is 10 by default, e.g. `parse("255", base: 10): u16 BE` gives `[0x00, 0xFF]`.
+///   The value is just like an integer literal without a suffix, so it could be used in arithmetic or
+///   casts, e.g. `parse("255") as u16: BE`. Invalid digits or a value above 128 bits are an error.
+/// * ASCII hex representations of element groups using the `ashex(0xDEu8, 0xADu8)` directive, writing
+///   `"DEAD"` instead of the bytes themselves. Use `ashex_lower` for lowercase digits, interpolated
+///   values could not be represented.
+/// * Enum discriminants using the `discriminant(Kind::A as u8)` directive, written with the width of the
+///   cast type. The macro could not evaluate the discriminant itself, so it is interpolated just like
+///   `#(Kind::A as u8): u8` and the explicit cast of a variant path is required.
+/// * Length-delimited blocks (`block_u16_be { 0x01u8, "hi" }`) prefix the bytes of their elements with their length,
+///   using the same widths as the length-prefixed strings (`block_u8`, `block_u16_le`, `block_u32_be`, ...).
+/// * Type-length-value records (`tlv(type: 0x01u8, { 0x0A0Bu16: BE, "ok" })`) write the type, the length of the value
+///   and its bytes, the length is a single byte unless named like a block (`tlv_u16_be(type: 0x01u8, { ... })`).
+/// * Variable-length integers (`varint(300u32)` writes `[0xAC, 0x02]`) in the unsigned LEB128 encoding of protobuf,
+///   `svarint(-1)` zigzags signed values first, the encoding is the same regardless of the endianness.
+/// * Fixed-size fields (`field("AB", size: 8, fill: 0x20, align: right)`) pad an element with the fill byte, `0x00`
+///   by default, after the bytes for `align: left`, the default, or before them for `align: right`.
+///     * Bytes which do not fit are an error unless `overflow: truncate` is given, keeping the leading ones.
+/// * Values in both byte orders using the `mirror(0x1234u16)` directive, writing the integer as little endian
+///   and then as big endian, `[0x34, 0x12, 0x12, 0x34]`, while `mirror_be(...)` writes big endian first.
 ///
 /// # Examples
 ///
@@ -75,6 +415,204 @@
 /// ```
 pub use bytify_impl::bytify;
 
+/// The same macro as [`bytify`] but appends the bytes to an existing buffer, instead of
+/// returning an array.
+///
+/// The first argument is the target buffer, which could be anything with an
+/// `extend_from_slice` method such as `Vec<u8>` or `&mut Vec<u8>`. Only available with the
+/// `alloc` feature, which is enabled by default.
+///
+/// # Examples
+///
+/// ```
+/// use bytify::bytify_into;
+///
+/// fn main() {
+///     let mut buffer = vec![0xFF];
+///     for _ in 0 .. 2 {
+///         bytify_into!(buffer, 1u8, 2u16: BE, "hi");
+///     }
+///     assert_eq!(buffer, [0xFF, 1, 0, 2, b'h', b'i', 1, 0, 2, b'h', b'i']);
+/// }
+/// ```
+///
+/// [`bytify`]: macro.bytify.html
+#[cfg(feature = "alloc")]
+pub use bytify_impl::bytify_into;
+
+/// The same macro as [`bytify`] but returns an owned `Vec<u8>`, instead of an array.
+///
+/// The vector is allocated by the crate invoking the macro, so it requires either `std` or
+/// `alloc` there.
+///
+/// # Examples
+///
+/// ```
+/// use bytify::{bytify, bytify_vec};
+///
+/// fn main() {
+///     let mut buffer = bytify_vec!("BMP", 0x1000u32: BE);
+///     buffer.push(0x00);
+///     assert_eq!(buffer.len(), 8);
+///     assert_eq!(buffer[.. 7], bytify!("BMP", 0x1000u32: BE));
+/// }
+/// ```
+///
+/// [`bytify`]: macro.bytify.html
+pub use bytify_impl::bytify_vec;
+
+/// The same macro as [`bytify`] but writes the bytes to an `std::io::Write` implementation,
+/// instead of returning an array.
+///
+/// The first argument is the target writer, the macro evaluates to the `std::io::Result<()>`
+/// of writing all the bytes at once. The expansion imports the `Write` trait on its own, so it need
+/// not be in scope.
+///
+/// # Examples
+///
+/// ```
+/// use bytify::{bytify, bytify_write};
+/// use std::io::{self, Write};
+///
+/// fn write_header<W: Write>(writer: &mut W) -> io::Result<()> {
+///     bytify_write!(writer, "BMP", 0x1000u32: BE)?;
+///     writer.flush()
+/// }
+///
+/// fn main() {
+///     let mut buffer = Vec::new();
+///     write_header(&mut buffer).unwrap();
+///     assert_eq!(buffer, bytify!("BMP", 0x1000u32: BE));
+/// }
+/// ```
+///
+/// [`bytify`]: macro.bytify.html
+pub use bytify_impl::bytify_write;
+
+/// The same macro as [`bytify`] but returns the length of the resulting array, instead of
+/// the array itself.
+///
+/// The length is expanded into a plain `usize` literal without the data itself, so it is usable
+/// anywhere a constant is expected, e.g. in array lengths or in a `const` assertion.
+///
+/// # Examples
+///
+/// ```
+/// use bytify::{bytify, bytify_len};
+///
+/// const HEADER: [u8; bytify_len!("BMP", 0x1000u32: BE)] = bytify!("BMP", 0x1000u32: BE);
+///
+/// assert_eq!(HEADER.len(), 7);
+/// ```
+///
+/// [`bytify`]: macro.bytify.html
+pub use bytify_impl::bytify_len;
+
+/// The same macro as [`bytify`] but declares a named constant holding the array, its length
+/// is computed automatically.
+///
+/// The name of the constant, optionally preceded by attributes and a visibility, goes first and
+/// is separated from the elements by a colon.
+///
+/// # Examples
+///
+/// ```
+/// use bytify::bytify_const;
+///
+/// bytify_const!(pub HEADER: 0x01u8, 0x02u16: BE, "ab");
+///
+/// fn main() {
+///     assert_eq!(HEADER, [0x01, 0x00, 0x02, b'a', b'b']);
+/// }
+/// ```
+///
+/// [`bytify`]: macro.bytify.html
+pub use bytify_impl::bytify_const;
+
+/// Converts a sequence of named numeric fields into a single byte array at the compile time,
+/// every field is written with the width of its declared type.
+///
+/// A field is written as `name: type = value`, the endianness could follow the type just like
+/// with interpolated values, e.g. `len: u32 BE = 16`. The names are only there for readability,
+/// the fields are written in order. A value is either a numeric literal, optionally negative or
+/// computed, or a path to a constant evaluated by the compiler. The `@be;` prefix sets the
+/// default endianness of all the fields.
+///
+/// # Examples
+///
+/// ```
+/// use bytify::bytify_struct;
+///
+/// const VERSION: u16 = 3;
+///
+/// fn main() {
+///     let header = bytify_struct! {
+///         @be;
+///         version: u16 = VERSION,
+///         flags: u8 = 0x03,
+///         offset: i32 LE = -2,
+///         scale: f32 = 1.5,
+///     };
+///     assert_eq!(header, [0x00, 0x03, 0x03, 0xFE, 0xFF, 0xFF, 0xFF, 0x3F, 0xC0, 0x00, 0x00]);
+/// }
+/// ```
+pub use bytify_impl::bytify_struct;
+
+/// Derives `to_bytes`, writing the fields of a struct at the run time with the same conventions
+/// as [`bytify_struct`], every field is written in order with the width of its type.
+///
+/// The fields must be of sized integer types, `f32`, `f64` or arrays of these. The endianness
+/// of all the fields is set by `#[bytify(endian = "be")]` on the struct, a field could override
+/// it with its own attribute. The method returns an `alloc::vec::Vec<u8>`, so it works in `no_std`
+/// crates with `alloc` as well. Only available with the `alloc` feature, which is enabled by default.
+///
+/// # Examples
+///
+/// ```
+/// use bytify::Bytify;
+///
+/// #[derive(Bytify)]
+/// #[bytify(endian = "be")]
+/// struct Header {
+///     version: u16,
+///     #[bytify(endian = "le")]
+///     offset: i32,
+///     magic: [u8; 2],
+/// }
+///
+/// fn main() {
+///     let header = Header { version: 3, offset: -2, magic: *b"BY" };
+///     assert_eq!(header.to_bytes(), [0x00, 0x03, 0xFE, 0xFF, 0xFF, 0xFF, b'B', b'Y']);
+/// }
+/// ```
+///
+/// [`bytify_struct`]: macro.bytify_struct.html
+#[cfg(feature = "alloc")]
+pub use bytify_impl::Bytify;
+
+/// The same macro as [`bytify`] but returns the offset and the length of every top level element
+/// as a `&'static str`, one element per line, instead of the array. Only available with the
+/// `trace` feature.
+///
+/// # Examples
+///
+/// ```
+/// use bytify::bytify_trace;
+///
+/// assert_eq!(bytify_trace!(1u8, "hi"), "element 0: offset 0, length 1\nelement 1: offset 1, length 2\n");
+/// ```
+///
+/// [`bytify`]: macro.bytify.html
+#[cfg(feature = "trace")]
+pub use bytify_impl::bytify_trace;
+
+/// Items the expansion of the macros refers to, these are not a part of the public API.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec::Vec;
+}
+
 /// The same macro as [`bytify`] but returns a slice, instead of array.
 ///
 /// [`bytify`]: macro.bytify.html
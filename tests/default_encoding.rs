@@ -0,0 +1,36 @@
+#![cfg(any(feature = "default-utf16le", feature = "default-utf16be"))]
+
+use bytify::bytify;
+
+#[cfg(feature = "default-utf16le")]
+#[test]
+fn default_utf16le() {
+    assert_eq!(bytify!("hi", 'é'), [b'h', 0x00, b'i', 0x00, 0xE9, 0x00]);
+    assert_eq!(bytify!("hi": utf8, 'é': utf8), [b'h', b'i', 0xC3, 0xA9]);
+    assert_eq!(bytify!("hi": utf16be, "a": BE), [0x00, b'h', 0x00, b'i', 0x00, b'a']);
+    assert_eq!(bytify!("a": cstr, "b": len_u8), [b'a', 0x00, 0x00, 0x00, 2, b'b', 0x00]);
+    assert_eq!(bytify!('a'..='b', b"ab"), [b'a', 0x00, b'b', 0x00, b'a', b'b']);
+}
+
+#[cfg(all(feature = "default-utf16be", not(feature = "default-utf16le")))]
+#[test]
+fn default_utf16be() {
+    assert_eq!(bytify!("hi", 'é'), [0x00, b'h', 0x00, b'i', 0x00, 0xE9]);
+    assert_eq!(bytify!("hi": utf8, 'é': utf8), [b'h', b'i', 0xC3, 0xA9]);
+    assert_eq!(bytify!("hi": utf16le, "a": LE), [b'h', 0x00, b'i', 0x00, b'a', 0x00]);
+    assert_eq!(bytify!("a": cstr, "b": len_u8), [0x00, b'a', 0x00, 0x00, 2, 0x00, b'b']);
+    assert_eq!(bytify!('a'..='b', b"ab"), [0x00, b'a', 0x00, b'b', b'a', b'b']);
+}
+
+#[cfg(all(feature = "default-utf16be", not(feature = "default-utf16le"), not(feature = "default-big-endian")))]
+#[test]
+fn numbers_keep_default_endianness() {
+    assert_eq!(bytify!(0x0102u16, "a", 0x0304u16: BE, "b": BE), [0x02, 0x01, 0x00, b'a', 0x03, 0x04, 0x00, b'b']);
+    assert_eq!(bytify!(le { 0x0102u16, "a" }), [0x02, 0x01, b'a', 0x00]);
+}
+
+#[cfg(all(feature = "default-utf16le", feature = "default-utf16be"))]
+#[test]
+fn default_utf16le_precedence() {
+    assert_eq!(bytify!("hi"), [b'h', 0x00, b'i', 0x00]);
+}
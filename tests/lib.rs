@@ -1,11 +1,15 @@
-#![cfg_attr(feature = "cargo-clippy", allow(explicit_iter_loop, unreadable_literal))]
+#![allow(clippy::explicit_iter_loop, clippy::unreadable_literal)]
+// Every macro is exercised here, `tests/no_std.rs` covers the build without the `alloc` feature.
+#![cfg(feature = "alloc")]
 
 use std::io::Cursor;
 use byteorder::{ReadBytesExt, LE};
-use bytify::bytify;
+use bytify::{bytify, bytify_const, bytify_into, bytify_len, bytify_struct, bytify_vec, bytify_write, Bytify};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Expected {
+    U128(u128),
+    I128(i128),
     U32(u32),
     I32(i32),
     U16(u16),
@@ -23,6 +27,12 @@ macro_rules! assert_bytify_eq {
         let mut reader = Cursor::new(&r[..]);
         for e in l.iter() {
             match *e {
+                Expected::U128(v) => {
+                    assert_eq!(reader.read_u128::<LE>().unwrap(), v);
+                },
+                Expected::I128(v) => {
+                    assert_eq!(reader.read_i128::<LE>().unwrap(), v);
+                },
                 Expected::U32(v) => {
                     assert_eq!(reader.read_u32::<LE>().unwrap(), v);
                 },
@@ -46,6 +56,37 @@ macro_rules! assert_bytify_eq {
     };
 }
 
+#[test]
+fn u128() {
+    assert_bytify_eq!(
+        bytify!(
+            18446744073709551616, 0xFFFFFFFFFFFFFFFFFF, 0xFFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF,
+            0u128, 0x10u128, 0xFFFFFFFFFFFFFFFFFFu128,
+        ),
+        [
+            U128(0x10000000000000000), U128(0xFFFFFFFFFFFFFFFFFF), U128(0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF),
+            U128(0), U128(0x10), U128(0xFFFFFFFFFFFFFFFFFF),
+        ]
+    );
+    assert_eq!(bytify!(0xFFFFFFFFFFFFFFFFFFu128), [
+        0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ]);
+}
+
+#[test]
+fn i128() {
+    assert_bytify_eq!(
+        bytify!(
+            -9223372036854775809, -170141183460469231731687303715884105728,
+            -1i128, -0x80000000000000000000000000000000i128, 0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFi128,
+        ),
+        [
+            I128(-9223372036854775809), I128(-170141183460469231731687303715884105728),
+            I128(-1), I128(i128::MIN), I128(i128::MAX),
+        ]
+    );
+}
+
 #[test]
 fn u32() {
     assert_bytify_eq!(
@@ -252,6 +293,433 @@ fn integer_endianness_ascription() {
     );
 }
 
+#[test]
+fn native_endianness_ascription() {
+    assert_eq!(bytify!(0x0102u16: ne), 0x0102u16.to_ne_bytes());
+    assert_eq!(bytify!(0xDEADBEAFu32: NE), 0xDEADBEAFu32.to_ne_bytes());
+    assert_eq!(bytify!(-2583.1f64: ne), (-2583.1f64).to_ne_bytes());
+}
+
+#[test]
+fn network_endianness_ascription() {
+    assert_eq!(bytify!(443u16: net), [0x01, 0xBB]);
+    assert_eq!(bytify!(443u16: NET, 0xDEADBEAF: net), bytify!(443u16: BE, 0xDEADBEAF: BE));
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn repeat() {
+    assert_eq!(bytify!([0u8; 16]), [0u8; 16]);
+    assert_eq!(bytify!([0xABu8: BE; 4]), [0xAB, 0xAB, 0xAB, 0xAB]);
+    assert_eq!(bytify!([0xABCDu16: BE; 2], [0xABCDu16; 2]: BE), [0xAB, 0xCD, 0xAB, 0xCD, 0xAB, 0xCD, 0xAB, 0xCD]);
+    assert_eq!(bytify!(1u8, ["ab"; 3], [-1i16; 0], 2u8), *b"\x01ababab\x02");
+}
+
+#[test]
+fn repeat_endianness() {
+    assert_eq!(bytify!(be { [0x0102u16; 2] }), [0x01, 0x02, 0x01, 0x02]);
+    assert_eq!(bytify!(be { [0x0102u16: LE; 2] }), [0x02, 0x01, 0x02, 0x01]);
+    assert_eq!(bytify!(be { [[0x0102u16; 2]; 2] }), [0x01, 0x02, 0x01, 0x02, 0x01, 0x02, 0x01, 0x02]);
+    assert_eq!(bytify!(be { [(0x0102u16, 0x0304u16: LE); 2] }), [0x01, 0x02, 0x04, 0x03, 0x01, 0x02, 0x04, 0x03]);
+    assert_eq!(bytify!(be { [-0x0102i16; 2], [0x0102u16 as u32; 1] }), [0xFE, 0xFE, 0xFE, 0xFE, 0x00, 0x00, 0x01, 0x02]);
+    assert_eq!(bytify!(@be; [0x0102u16; 2], le { [0x0102u16; 2] }), [0x01, 0x02, 0x01, 0x02, 0x02, 0x01, 0x02, 0x01]);
+}
+
+#[test]
+fn empty() {
+    let empty: [u8; 0] = bytify!();
+    assert_eq!(empty, []);
+    assert_eq!(bytify!(@be;), []);
+    assert_eq!(bytify!(1u8,), [0x01]);
+    assert_eq!(bytify!(1u8, 0x0203u16: BE,), [0x01, 0x02, 0x03]);
+    let mut buffer = vec![0xFF];
+    bytify_into!(buffer);
+    bytify_into!(buffer,);
+    assert_eq!(buffer, [0xFF]);
+}
+
+#[test]
+fn array() {
+    assert_eq!(bytify!([0x01, 0x02], [0x03, 0x04]), bytify!(0x01, 0x02, 0x03, 0x04));
+    assert_eq!(bytify!([], [[1u8], [2u8, [3u8]]], []), [1, 2, 3]);
+    assert_eq!(
+        bytify!(["ab", 0xFACE: BE], [0xFACE: LE, -1i16, [0u8; 2]]),
+        bytify!("ab", 0xFACE: BE, 0xFACE: LE, -1i16, 0u8, 0u8)
+    );
+}
+
+#[test]
+fn signed_width() {
+    assert_eq!(bytify!(-1), [0xFF]);
+    assert_eq!(bytify!(-1: i32), [0xFF, 0xFF, 0xFF, 0xFF]);
+    assert_eq!(bytify!(@i16; -1, -2i8, 1), [0xFF, 0xFF, 0xFE, 0x01]);
+    assert_eq!(bytify!(i32 { -(1 + 1), 0 - 3 }: BE), [0xFF, 0xFF, 0xFF, 0xFE, 0xFF, 0xFF, 0xFF, 0xFD]);
+    assert_eq!(bytify!(-32768: i16), [0x00, 0x80]);
+    assert_eq!(bytify!(@i32; -1: i8), [0xFF]);
+}
+
+#[test]
+fn endianness_words() {
+    assert_eq!(bytify!(0x1234u16: big), bytify!(0x1234u16: BE));
+    assert_eq!(bytify!(0x1234u16: little), bytify!(0x1234u16: LE));
+    assert_eq!(bytify!(big { 1u16, 2u16: little }, 3u16 big), [0x00, 0x01, 0x02, 0x00, 0x00, 0x03]);
+    assert_eq!(bytify!(@big; -2i32, 1.0f32), bytify!(-2i32: BE, 1.0f32: BE));
+}
+
+#[test]
+fn postfix_endianness() {
+    assert_eq!(bytify!(0x1234u16 be), bytify!(0x1234u16: be));
+    assert_eq!(bytify!(0x1234u16 BE, 0x1234u16 le), [0x12, 0x34, 0x34, 0x12]);
+    assert_eq!(bytify!(-2i32 NET, 1.5f32 be), bytify!(-2i32: NET, 1.5f32: be));
+    assert_eq!(bytify!([1u16, 2u16] be, (3u16 LE,) BE), [0x00, 0x01, 0x00, 0x02, 0x03, 0x00]);
+}
+
+#[test]
+fn array_endianness() {
+    assert_eq!(bytify!([1u16, 2u16]: BE), [0x00, 0x01, 0x00, 0x02]);
+    assert_eq!(bytify!([1u16, 2u16: LE]: BE), [0x00, 0x01, 0x02, 0x00]);
+    assert_eq!(bytify!([1u16, [2u16, 3u16]]: be), [0x00, 0x01, 0x00, 0x02, 0x00, 0x03]);
+    assert_eq!(bytify!([1u16, [2u16]: LE]: BE), [0x00, 0x01, 0x02, 0x00]);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn large() {
+    let output = bytify!([0xABCDu16: BE; 4096], [[1u8, 2u8]; 1024], "tail");
+    assert_eq!(output.len(), 4096 * 2 + 1024 * 2 + 4);
+    assert!(output[.. 8192].chunks(2).all(|pair| pair == [0xAB, 0xCD]));
+    assert!(output[8192 .. 10240].chunks(2).all(|pair| pair == [1, 2]));
+    assert_eq!(&output[10240 ..], b"tail");
+}
+
+#[test]
+fn every_byte() {
+    let output = bytify!(
+        "000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F": hex,
+        "202122232425262728292A2B2C2D2E2F303132333435363738393A3B3C3D3E3F": hex,
+        "404142434445464748494A4B4C4D4E4F505152535455565758595A5B5C5D5E5F": hex,
+        "606162636465666768696A6B6C6D6E6F707172737475767778797A7B7C7D7E7F": hex,
+        "808182838485868788898A8B8C8D8E8F909192939495969798999A9B9C9D9E9F": hex,
+        "A0A1A2A3A4A5A6A7A8A9AAABACADAEAFB0B1B2B3B4B5B6B7B8B9BABBBCBDBEBF": hex,
+        "C0C1C2C3C4C5C6C7C8C9CACBCCCDCECFD0D1D2D3D4D5D6D7D8D9DADBDCDDDEDF": hex,
+        "E0E1E2E3E4E5E6E7E8E9EAEBECEDEEEFF0F1F2F3F4F5F6F7F8F9FAFBFCFDFEFF": hex,
+    );
+    assert!((0 ..= 255).eq(output.iter().copied()));
+    assert_eq!(bytify!(b"\"\\\0'\r\n"), *b"\"\\\0'\r\n");
+}
+
+#[test]
+fn vec() {
+    let buffer: Vec<u8> = bytify_vec!(1u8, 0x0203u16: BE, "hi");
+    assert_eq!(buffer, bytify!(1u8, 0x0203u16: BE, "hi"));
+    assert_eq!(bytify_vec!(), Vec::<u8>::new());
+    assert_eq!(bytify_vec!(@be; #VERSION: u16, 0u8), [0x01, 0x02, 0x00]);
+}
+
+#[test]
+fn write() {
+    let mut buffer = Vec::new();
+    bytify_write!(buffer, 1u16: BE, "hi").unwrap();
+    bytify_write!(&mut buffer, @be; #VERSION: u16, 0xFFu8).unwrap();
+    assert_eq!(buffer, bytify!(1u16: BE, "hi", 0x0102u16: BE, 0xFFu8));
+    let mut cursor = Cursor::new([0u8; 2]);
+    assert!(bytify_write!(cursor, 1u32).is_err());
+}
+
+#[test]
+fn write_generic() {
+    // `Write` is not in scope here, the writer is a mutable reference.
+    fn write_header<W: std::io::Write>(writer: &mut W) -> std::io::Result<()> {
+        bytify_write!(writer, b"BM", 0x0102u16: BE)
+    }
+    let mut buffer: Vec<u8> = Vec::new();
+    write_header(&mut buffer).unwrap();
+    write_header(&mut &mut buffer).unwrap();
+    assert_eq!(buffer, [b'B', b'M', 0x01, 0x02, b'B', b'M', 0x01, 0x02]);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn paren() {
+    assert_eq!(bytify!((0x1234u16): BE), [0x12, 0x34]);
+    assert_eq!(bytify!(((0x1234u16)): BE, ((0x1234u16: BE))), [0x12, 0x34, 0x12, 0x34]);
+    assert_eq!(bytify!(("ab"), (-1i8), ([1u8; 2])), *b"ab\xFF\x01\x01");
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn tuple() {
+    assert_eq!(bytify!((1u8, 2u16: BE, "x")), bytify!(1u8, 2u16: BE, "x"));
+    assert_eq!(bytify!((1u8, 2u16), (0x0304u16,)), [0x01, 0x02, 0x00, 0x04, 0x03]);
+    assert_eq!(bytify!((0x0102u16, (0x0304u16: LE, 5u8)): BE), [0x01, 0x02, 0x04, 0x03, 0x05]);
+    assert_eq!(bytify!((), [(1u8, 'a'); 2]), [0x01, b'a', 0x01, b'a']);
+}
+
+#[test]
+fn parse() {
+    assert_eq!(bytify!(parse("255", 10) as u16: BE), [0x00, 0xFF]);
+    assert_eq!(bytify!(parse("255"), parse("-1")), [0xFF, 0xFF]);
+    assert_eq!(bytify!(parse("DEADBEEF", 16): BE), [0xDE, 0xAD, 0xBE, 0xEF]);
+    assert_eq!(bytify!(parse("ff", 16) as u32, parse("101", 2)), [0xFF, 0x00, 0x00, 0x00, 0x05]);
+    assert_eq!(bytify!((parse("z", 36) + 1) as u8), [36]);
+    assert_eq!(bytify!(parse("255", base: 10): u16 BE), [0x00, 0xFF]);
+    assert_eq!(bytify!(parse("ff", base: 16): u32 LE, parse("-1"): i16), [0xFF, 0x00, 0x00, 0x00, 0xFF, 0xFF]);
+    assert_eq!(bytify!([parse("10", base: 2) + 1; 2]: u16 BE), [0x00, 0x03, 0x00, 0x03]);
+}
+
+#[test]
+fn unsigned_width() {
+    assert_eq!(bytify!(5: u16), [0x05, 0x00]);
+    assert_eq!(bytify!(5: u32 BE, 0x0102: u16 BE), [0x00, 0x00, 0x00, 0x05, 0x01, 0x02]);
+    assert_eq!(bytify!(@u16; 1, 2u8, 'a'), [0x01, 0x00, 0x02, b'a', 0x00]);
+    assert_eq!(bytify!(u8 { 0xFF, 1 + 1 }), [0xFF, 0x02]);
+}
+
+#[test]
+fn ramp() {
+    assert_eq!(bytify!(ramp(0u8, 4, 2)), [0, 2, 4, 6]);
+    assert_eq!(bytify!(ramp(0, 4)), [0, 1, 2, 3]);
+    assert_eq!(bytify!(ramp(3, 3, -2)), [0x03, 0x01, 0xFF]);
+    assert_eq!(bytify!(ramp(0, 3, 0x80)), [0x00, 0x00, 0x80, 0x00, 0x00, 0x01]);
+    assert_eq!(bytify!(ramp(0x0100u16, 2, 1): BE), [0x01, 0x00, 0x01, 0x01]);
+    assert_eq!(bytify!(ramp(0u8, 0, 1), ramp(7, 1, 100)), [0x07]);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn range() {
+    assert_eq!(bytify!('a'..='e'), *b"abcde");
+    assert_eq!(bytify!('a'..'c', 'x'..='x'), *b"abx");
+    assert_eq!(bytify!(('а'..='в'): utf16le), [0x30, 0x04, 0x31, 0x04, 0x32, 0x04]);
+    assert_eq!(bytify!(0u8..=3u8), [0, 1, 2, 3]);
+    assert_eq!(bytify!(0..4), [0, 1, 2, 3]);
+    assert_eq!(bytify!((0x00FEu16..=0x0100): BE), [0x00, 0xFE, 0x00, 0xFF, 0x01, 0x00]);
+    assert_eq!(bytify!(254..=256), bytify!(254u16, 255u16, 256u16));
+    assert_eq!(bytify!(-1..=1), bytify!(-1i8, 0i8, 1i8));
+    assert_eq!(bytify!(-1..=200), bytify!(-1i16..=200i16));
+    assert_eq!(bytify!((-1..=200): BE)[.. 6], [0xFF, 0xFF, 0x00, 0x00, 0x00, 0x01]);
+    let empty: [u8; 0] = bytify!(3..3, 'a'..'a');
+    assert_eq!(empty, []);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn string_repeat() {
+    assert_eq!(bytify!("ab" * 2), *b"abab");
+    assert_eq!(bytify!(3 * "-", b"\x00" * 2), *b"---\x00\x00");
+    assert_eq!(bytify!(("é" * 2): utf16be), [0x00, 0xE9, 0x00, 0xE9]);
+    assert_eq!(bytify!(1u8, "ab" * 0, 2u8), [1, 2]);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn group() {
+    macro_rules! forward {
+        ($($items:expr),*) => (bytify!($($items),*));
+    }
+    macro_rules! forward_be {
+        ($($items:expr),*) => (bytify!($($items: BE),*));
+    }
+    assert_eq!(forward!(0x1234u16, -1i8, "ab"), bytify!(0x1234u16, -1i8, "ab"));
+    assert_eq!(forward_be!(0x1234u16, -2i16), [0x12, 0x34, 0xFF, 0xFE]);
+    macro_rules! record {
+        ($kind:expr, $count:expr, $name:expr) => (bytify!(fill($kind, $count), [$kind; $count], $kind as u16: BE, $name: len_u8, ramp($kind, $count), tlv(type: $kind, { $name: utf16le })));
+    }
+    assert_eq!(
+        record!(0x07u8, 2, "ab"),
+        [0x07, 0x07, 0x07, 0x07, 0x00, 0x07, 0x02, b'a', b'b', 0x07, 0x08, 0x07, 0x04, b'a', 0x00, b'b', 0x00]
+    );
+}
+
+#[test]
+fn constant_arithmetic() {
+    assert_eq!(bytify!(0x10 + 0x20), [0x30]);
+    assert_eq!(bytify!(1024u32 * 4: BE), [0x00, 0x00, 0x10, 0x00]);
+    assert_eq!(bytify!((1024 * 4): BE, 1024 * 4: BE), [0x10, 0x00, 0x10, 0x00]);
+    assert_eq!(bytify!(200u8 + 55, 1u16 + 1), [0xFF, 0x02, 0x00]);
+    assert_bytify_eq!(
+        bytify!(
+            10 - 20, -(1 + 2), 7 / 2, 7 % 2, -7 / 2, -7 % 2, 2 + 3 * 4, (2 + 3) * 4, -1i32 * 2,
+        ),
+        [
+            I8(-10), I8(-3), U8(3), U8(1), I8(-3), I8(-1), U8(14), U8(20), I32(-2),
+        ]
+    );
+}
+
+#[test]
+fn constant_bitwise() {
+    assert_eq!(bytify!((0xF0 | 0x0F) as u8), [0xFF]);
+    assert_eq!(bytify!((1 << 0) | (1 << 3) as u8), [0x09]);
+    assert_eq!(bytify!(0xFF & 0x0F, 0xF0 ^ 0xFF, 0x1234 >> 8, (0x1234 >> 4) as u8), [0x0F, 0x0F, 0x12, 0x23]);
+    assert_eq!(bytify!(!0u8, !0, !0x0F, !-1, !0i8), [0xFF, 0xFF, 0xF0, 0x00, 0xFF]);
+    assert_eq!(bytify!(!0u16: BE, 1u16 << 15: BE, (1 << 15): BE), [0xFF, 0xFF, 0x80, 0x00, 0x80, 0x00]);
+    assert_bytify_eq!(
+        bytify!(
+            0xFFu8 << 4, 0x80u8 >> 7, -8 >> 1, -7i8 >> 1, -128i8 >> 7, -1i16 & 0x7FFF, -1 ^ 1,
+        ),
+        [
+            U8(0xF0), U8(0x01), I8(-4), I8(-4), I8(-1), I16(0x7FFF), I8(-2),
+        ]
+    );
+}
+
+#[test]
+fn cast() {
+    assert_eq!(bytify!(0x01 as u32: BE, 0xFF as u16), [0x00, 0x00, 0x00, 0x01, 0xFF, 0x00]);
+    assert_eq!(bytify!(-1 as i32, 300 as u128: BE), [
+        0xFF, 0xFF, 0xFF, 0xFF,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x2C,
+    ]);
+    assert_eq!(bytify!(1 as f32), 1.0f32.to_le_bytes());
+    assert_eq!(bytify!(-2.5 as f64), (-2.5f64).to_le_bytes());
+    assert_eq!(bytify!(1.5f64 as f32: BE), 1.5f32.to_be_bytes());
+    assert_eq!(bytify!(255u8 as f64: BE, -3i8 as f32), bytify!(255.0f64: BE, -3.0f32));
+    assert_eq!(bytify!(2.9 as u8, -1.5 as u8, 300.0 as u8, -200.0 as i8, 1e10 as u32: BE), [
+        0x02, 0x00, 0xFF, 0x80, 0xFF, 0xFF, 0xFF, 0xFF,
+    ]);
+}
+
+#[test]
+fn cast_truncation() {
+    assert_eq!(bytify!(0x1FF as u8), [0xFF]);
+    assert_eq!(bytify!(0x1FFu16 as u8, -1 as u8, 200 as i8, 0x12345 as u16: BE), [0xFF, 0xFF, 0xC8, 0x23, 0x45]);
+    assert_eq!(bytify!(-1i64 as u32, 0x1_0000_0001u64 as i32), [0xFF, 0xFF, 0xFF, 0xFF, 0x01, 0x00, 0x00, 0x00]);
+}
+
+#[test]
+fn cast_extension() {
+    assert_eq!(bytify!(-1i8 as i32: BE), [0xFF, 0xFF, 0xFF, 0xFF]);
+    assert_eq!(bytify!(-1i8 as u32: BE), [0xFF, 0xFF, 0xFF, 0xFF]);
+    assert_eq!(bytify!(-2i16 as i64: BE), [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE]);
+    assert_eq!(bytify!(0xFFu8 as u32: BE), [0x00, 0x00, 0x00, 0xFF]);
+    assert_eq!(bytify!(0xFFu8 as i32: BE), [0x00, 0x00, 0x00, 0xFF]);
+    assert_eq!(bytify!(0x80u8 as i16: BE, 0x8000u16 as u64: BE), [
+        0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00,
+    ]);
+    assert_eq!(bytify!(0xFFu8 as i8 as i16: BE, -1i8 as u8 as u16: BE), [0xFF, 0xFF, 0x00, 0xFF]);
+}
+
+#[test]
+fn radix() {
+    assert_eq!(bytify!(0b1010, 0o12, 0xA, 10), [10, 10, 10, 10]);
+    assert_eq!(bytify!(0b1_0000_0000, 0o400, 0x100, 256), bytify!(256u16, 256u16, 256u16, 256u16));
+    assert_eq!(bytify!(0b1u32: BE, 0o1u32: BE, 0x1u32: BE), bytify!(1u32: BE, 1u32: BE, 1u32: BE));
+    assert_eq!(bytify!(-0b1000_0000, -0o200, -0x80, -128), [0x80, 0x80, 0x80, 0x80]);
+    assert_eq!(bytify!(-0b1000_0001, -0o201, -0x81), bytify!(-129i16, -129i16, -129i16));
+    assert_eq!(bytify!(0xFFFF_FFFF, 0o37777777777, 0b11111111111111111111111111111111), [0xFF; 12]);
+    // A leading zero does not make a literal octal.
+    assert_eq!(bytify!(017, 0017u16: BE), [17, 0x00, 17]);
+}
+
+#[test]
+fn negative_hex() {
+    assert_eq!(bytify!(-0x80i8, -0x7Fi8, -0x01i8, -0x00i8), [0x80, 0x81, 0xFF, 0x00]);
+    assert_eq!(bytify!(-0x8000i16: BE, -0x7FFFi16: BE, -0x100i16: BE, -0x80i16: BE), [0x80, 0x00, 0x80, 0x01, 0xFF, 0x00, 0xFF, 0x80]);
+    assert_eq!(bytify!(-0x8000_0000i32: BE, -0x1_0000i32: BE, -0xFFi32: BE), [0x80, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0x01]);
+    assert_eq!(bytify!(-0x8000_0000_0000_0000i64: BE), [0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    assert_eq!(bytify!(-0x1_0000_0000i64: BE), [0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00]);
+    assert_eq!(bytify!(-0x8000_0000_0000_0000_0000_0000_0000_0000i128: BE), i128::MIN.to_be_bytes());
+    assert_eq!(bytify!(-0x1i128: BE), (-1i128).to_be_bytes());
+    // The two's complement of a value in an unsigned width is written without the sign.
+    assert_eq!(bytify!(0x80u8, 0x8000u16: BE), bytify!(-0x80i8, -0x8000i16: BE));
+}
+
+#[test]
+fn negative_radix() {
+    assert_eq!(bytify!(-0x80i8, -0o200i8, -0b1000_0000i8), [0x80, 0x80, 0x80]);
+    assert_eq!(bytify!(-0x1i8, -0o1i8, -0b1i8), [0xFF, 0xFF, 0xFF]);
+    assert_eq!(bytify!(-0x8000i16: BE, -0o100000i16: BE, -0b1000_0000_0000_0000i16: BE), [0x80, 0x00, 0x80, 0x00, 0x80, 0x00]);
+    assert_eq!(bytify!(-0xFFi16: BE, -0o377i16: BE, -0b1111_1111i16: BE), [0xFF, 0x01, 0xFF, 0x01, 0xFF, 0x01]);
+    assert_eq!(bytify!(-0x8000_0000i32, -0o20000000000i32), bytify!(-2147483648i32, -2147483648i32));
+    assert_eq!(bytify!(-0x8000_0000_0000_0000i64, -0b1i64), bytify!(-9223372036854775808i64, -1i64));
+    assert_eq!(bytify!(-0x8000_0000_0000_0000_0000_0000_0000_0000i128), i128::MIN.to_le_bytes());
+    assert_eq!(bytify!(-0x80, -0x81, -0x8001), bytify!(-128i8, -129i16, -32769i32));
+}
+
+#[test]
+fn digit_separators() {
+    assert_eq!(bytify!(0xDEAD_BEEF), bytify!(0xDEADBEEF));
+    assert_eq!(bytify!(0xDEAD_BEEF: BE), [0xDE, 0xAD, 0xBE, 0xEF]);
+    assert_eq!(bytify!(1_000_000), bytify!(1000000));
+    assert_eq!(bytify!(-1_000_i32), bytify!(-1000i32));
+    assert_eq!(bytify!(0o17_77), bytify!(0o1777));
+    assert_eq!(bytify!(0b1111_1111_1, 0b_1_0), bytify!(0b111111111, 0b10));
+    assert_eq!(bytify!(0x__FF__u8, 0xFF_i16), bytify!(0xFFu8, 0xFFi16));
+    assert_eq!(bytify!(1_000.5_f64, 0.062_5), bytify!(1000.5f64, 0.0625));
+    assert_eq!(bytify!([0u8; 1_0], [1u8; 0x_2]), [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1]);
+}
+
+#[test]
+fn float_suffixed() {
+    assert_eq!(bytify!(1.5f32, -1.5f32: BE), [0x00, 0x00, 0xC0, 0x3F, 0xBF, 0xC0, 0x00, 0x00]);
+    assert_eq!(bytify!(1e40f64), 1e40f64.to_le_bytes());
+}
+
+#[test]
+fn float_unsuffixed() {
+    // Always f32, a value it does not hold exactly (`0.1`, `1e40`) needs a suffix.
+    assert_eq!(bytify!(0.5), 0.5f32.to_le_bytes());
+    assert_eq!(bytify!(-0.25: BE), (-0.25f32).to_be_bytes());
+    assert_eq!(bytify!(16777216.0), 16777216f32.to_le_bytes());
+    assert_eq!(bytify!(0.1f32), 0.1f32.to_le_bytes());
+    assert_eq!(bytify!(0.1f64), 0.1f64.to_le_bytes());
+    assert_eq!(bytify!(-0.1f64: BE), (-0.1f64).to_be_bytes());
+    assert_eq!(bytify!(16777217.0f64), 16777217f64.to_le_bytes());
+}
+
+#[test]
+fn float_endianness() {
+    let be = bytify!(-1.5f32: BE);
+    let mut le = bytify!(-1.5f32: LE);
+    le.reverse();
+    assert_eq!(be, le);
+    assert_eq!(be, [0xBF, 0xC0, 0x00, 0x00]);
+    assert_eq!(bytify!(1.5f32: BE, 1.5f32: LE), [0x3F, 0xC0, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x3F]);
+    assert_eq!(bytify!(-2.5f64: BE), (-2.5f64).to_be_bytes());
+    assert_eq!(bytify!(-2.5f64: LE), (-2.5f64).to_le_bytes());
+    assert_eq!(bytify!(-(1.5f32): BE, -(1 as f32): BE), [0xBF, 0xC0, 0x00, 0x00, 0xBF, 0x80, 0x00, 0x00]);
+    assert_eq!(bytify!(be { -1.5f32, 1.5f32 }), bytify!(-1.5f32: BE, 1.5f32: BE));
+    assert_eq!(bytify!(@be; -1.5f32, -1.5f32: LE), [0xBF, 0xC0, 0x00, 0x00, 0x00, 0x00, 0xC0, 0xBF]);
+}
+
+#[test]
+fn float_strict() {
+    assert_eq!(bytify!(0.1f32), 0.1f32.to_le_bytes());
+    assert_eq!(bytify!(1.5f32: f32_strict, -0.25: f32_strict), bytify!(1.5f32, -0.25f32));
+    assert_eq!(bytify!(0.5: f32_strict: BE, 16777216.0: f32_strict), bytify!(0.5f32: BE, 16777216.0f32));
+    assert_eq!(bytify!(0.1f64: f32_strict), 0.1f64.to_le_bytes());
+    assert_eq!(bytify!(f32_strict { 1.0, -(2.5 as f32), 3 as f32 }), bytify!(1.0f32, -2.5f32, 3.0f32));
+    assert_eq!(bytify_struct!(@f32_strict; scale: f32 = 0.75), 0.75f32.to_le_bytes());
+}
+
+#[test]
+fn float_negative_zero() {
+    assert_eq!(bytify!(-0.0f32: BE), [0x80, 0x00, 0x00, 0x00]);
+    assert_eq!(bytify!(-0.0f64: BE), [0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    assert_eq!(bytify!(-0.0: BE), [0x80, 0x00, 0x00, 0x00]);
+    assert_eq!(bytify!(0.0f32: BE), [0x00, 0x00, 0x00, 0x00]);
+    assert_eq!(bytify!((-0.0 as f64): BE), [0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    assert_eq!(bytify!(-(0.0 as f32): BE), [0x80, 0x00, 0x00, 0x00]);
+    assert_eq!(bytify!([-0.0f32; 2]), [0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80]);
+}
+
+#[test]
+fn float_half() {
+    assert_eq!(bytify!(1.0f16: BE), [0x3C, 0x00]);
+    assert_eq!(bytify!(1.5f16, -2.0f16: BE), [0x00, 0x3E, 0xC0, 0x00]);
+    assert_eq!(bytify!(0.0f16, -0.0f16: BE), [0x00, 0x00, 0x80, 0x00]);
+    assert_eq!(bytify!(65504.0f16: BE), [0x7B, 0xFF]);
+    assert_eq!(bytify!(65519.0f16: BE, -65519.0f16: BE), [0x7B, 0xFF, 0xFB, 0xFF]);
+    assert_eq!(bytify!(0.1f16: BE), [0x2E, 0x66]);
+    assert_eq!(bytify!(6.103515625e-5f16: BE), [0x04, 0x00]);
+    assert_eq!(bytify!(5.960464477539063e-8f16: BE), [0x00, 0x01]);
+    assert_eq!(bytify!(1e-10f16: BE), [0x00, 0x00]);
+    assert_eq!(bytify!(2049.0f16, 2051.0f16: BE), [0x00, 0x68, 0x68, 0x02]);
+    assert_eq!(bytify!((1 as f16): BE), [0x3C, 0x00]);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
 #[test]
 fn char() {
     assert_bytify_eq!(
@@ -268,6 +736,7 @@ fn char() {
     );
 }
 
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
 #[test]
 fn string() {
     assert_bytify_eq!(
@@ -283,3 +752,620 @@ fn string() {
         ]
     );
 }
+
+#[test]
+fn byte_string() {
+    assert_bytify_eq!(
+        bytify!(
+            b"\x01\x02\xFF", b"\x00\x00", b"Hi\0!",
+        ),
+        [
+            U8(0x01), U8(0x02), U8(0xFF),
+            U8(0x00), U8(0x00),
+            U8(0x48), U8(0x69), U8(0x00), U8(0x21),
+        ]
+    );
+    assert_eq!(bytify!(b"\x01\x02\xFF"), [1, 2, 255]);
+}
+
+#[test]
+fn byte() {
+    assert_bytify_eq!(
+        bytify!(
+            b'A', b'\n', b'\x00', b'\xFF',
+        ),
+        [
+            U8(0x41), U8(0x0A), U8(0x00), U8(0xFF),
+        ]
+    );
+    assert_eq!(bytify!(b'A', b'\n'), [65, 10]);
+}
+
+#[test]
+fn bool() {
+    assert_bytify_eq!(
+        bytify!(
+            true, false, false, true,
+        ),
+        [
+            U8(0x01), U8(0x00), U8(0x00), U8(0x01),
+        ]
+    );
+    assert_eq!(bytify!(true), [1]);
+    assert_eq!(bytify!(false), [0]);
+}
+
+#[test]
+fn into() {
+    let mut buffer = Vec::new();
+    bytify_into!(buffer);
+    bytify_into!(buffer, 1u8, 0xFACEu16: BE, "hi", -1i32,);
+    assert_eq!(buffer, bytify!(1u8, 0xFACEu16: BE, "hi", -1i32));
+    let reference = &mut buffer;
+    bytify_into!(reference, 'Я');
+    assert_eq!(buffer, bytify!(1u8, 0xFACEu16: BE, "hi", -1i32, 'Я'));
+}
+
+#[test]
+fn ascii() {
+    assert_eq!(bytify!("Hello, world!": ascii), *b"Hello, world!");
+    assert_eq!(bytify!('A': ascii, "": ascii), [b'A']);
+    assert_eq!(bytify!("AT": ascii: cstr), [b'A', b'T', 0x00]);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn latin1() {
+    assert_eq!(bytify!("café": latin1), [b'c', b'a', b'f', 0xE9]);
+    assert_eq!(bytify!("café"), [b'c', b'a', b'f', 0xC3, 0xA9]);
+    assert_eq!(bytify!('ÿ': latin1, "ß¿": latin1: cstr), [0xFF, 0xDF, 0xBF, 0x00]);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn char_width() {
+    assert_eq!(bytify!('é'), [0xC3, 0xA9]);
+    assert_eq!(bytify!('é': u8), [0xE9]);
+    assert_eq!(bytify!('A': u16 LE, 'A': u16 BE), [0x41, 0x00, 0x00, 0x41]);
+    assert_eq!(bytify!('Я': u16: BE), [0x04, 0x2F]);
+    assert_eq!(bytify!('😀': u32 BE), [0x00, 0x01, 0xF6, 0x00]);
+    assert_eq!(bytify!(be { 'A': u32 }), [0x00, 0x00, 0x00, 0x41]);
+    assert_eq!(bytify!("hé": u8: cstr), [b'h', 0xE9, 0x00]);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn utf16() {
+    assert_eq!(bytify!("hi": utf16le), [b'h', 0x00, b'i', 0x00]);
+    assert_eq!(bytify!("é": utf16le), [0xE9, 0x00]);
+    assert_eq!(bytify!("é": utf16be), [0x00, 0xE9]);
+    assert_eq!(bytify!('é': utf16le, "é"), [0xE9, 0x00, 0xC3, 0xA9]);
+    assert_eq!(bytify!("😀": utf16le), [0x3D, 0xD8, 0x00, 0xDE]);
+    assert_eq!(bytify!("a😀": utf16be), [0x00, b'a', 0xD8, 0x3D, 0xDE, 0x00]);
+    assert_eq!(bytify!(["é", 'é']: utf16be), [0x00, 0xE9, 0x00, 0xE9]);
+}
+
+#[test]
+fn bom() {
+    assert_eq!(bytify!("hi": utf8_bom), [0xEF, 0xBB, 0xBF, b'h', b'i']);
+    assert_eq!(bytify!("hi": utf16le_bom), [0xFF, 0xFE, b'h', 0x00, b'i', 0x00]);
+    assert_eq!(bytify!("hi": utf16be_bom), [0xFE, 0xFF, 0x00, b'h', 0x00, b'i']);
+    assert_eq!(bytify!("": utf8_bom: len_u8), [0x03, 0xEF, 0xBB, 0xBF]);
+    assert_eq!(bytify!(utf16le_bom { "a", "b": utf8 }), [0xFF, 0xFE, b'a', 0x00, b'b']);
+}
+
+#[test]
+fn utf32() {
+    assert_eq!(bytify!("hi": utf32le), [b'h', 0x00, 0x00, 0x00, b'i', 0x00, 0x00, 0x00]);
+    assert_eq!(bytify!("é": utf32be), [0x00, 0x00, 0x00, 0xE9]);
+    assert_eq!(bytify!("😀": utf32le), [0x00, 0xF6, 0x01, 0x00]);
+    assert_eq!(bytify!('😀': utf32be), [0x00, 0x01, 0xF6, 0x00]);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn cstr() {
+    assert_eq!(bytify!("hi": cstr), [104, 105, 0]);
+    assert_eq!(bytify!("": cstr), [0]);
+    assert_eq!(bytify!("é": cstr, "a"), [0xC3, 0xA9, 0x00, b'a']);
+    assert_eq!(bytify!(["a", "b"]: cstr), [b'a', 0x00, b'b', 0x00]);
+    assert_eq!(bytify!("hi": utf16le: cstr), [b'h', 0x00, b'i', 0x00, 0x00, 0x00]);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn length_prefixed() {
+    assert_eq!(bytify!("hello": len_u8), [5, b'h', b'e', b'l', b'l', b'o']);
+    assert_eq!(bytify!("hi": len_u16_be), [0x00, 0x02, b'h', b'i']);
+    assert_eq!(bytify!("hi": len_u16_le), [0x02, 0x00, b'h', b'i']);
+    assert_eq!(bytify!("hi": len_u32_be), [0x00, 0x00, 0x00, 0x02, b'h', b'i']);
+    assert_eq!(bytify!("hi": len_u32_le), [0x02, 0x00, 0x00, 0x00, b'h', b'i']);
+    assert_eq!(bytify!("": len_u8), [0]);
+    assert_eq!(bytify!("é": len_u8), [2, 0xC3, 0xA9]);
+    assert_eq!(bytify!("é": utf16be: len_u8), [2, 0x00, 0xE9]);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn length_prefixed_unit() {
+    assert_eq!(bytify!("héllo": len_u16_be_chars), [0x00, 0x05, b'h', 0xC3, 0xA9, b'l', b'l', b'o']);
+    assert_eq!(bytify!("héllo": len_u16_be_bytes), [0x00, 0x06, b'h', 0xC3, 0xA9, b'l', b'l', b'o']);
+    assert_eq!(bytify!("héllo": len_u16_be_bytes), bytify!("héllo": len_u16_be));
+    assert_eq!(bytify!("😀": utf16le: len_u8_chars), [0x01, 0x3D, 0xD8, 0x00, 0xDE]);
+    assert_eq!(bytify!("😀": utf16le: len_u32_le_bytes), [0x04, 0x00, 0x00, 0x00, 0x3D, 0xD8, 0x00, 0xDE]);
+}
+
+#[test]
+fn hex() {
+    assert_eq!(bytify!("deadbeef": hex), [0xDE, 0xAD, 0xBE, 0xEF]);
+    assert_eq!(bytify!("DEAD BEEF 00": hex), [0xDE, 0xAD, 0xBE, 0xEF, 0x00]);
+    assert_eq!(bytify!("0a\n0B\t0c": hex, 1u8), [0x0A, 0x0B, 0x0C, 0x01]);
+    let empty: [u8; 0] = bytify!("": hex);
+    assert_eq!(empty, []);
+    assert_eq!(bytify!("cafe": hex: len_u8), [0x02, 0xCA, 0xFE]);
+}
+
+#[test]
+fn base64() {
+    assert_eq!(bytify!("SGk=": base64), [72, 105]);
+    assert_eq!(bytify!("SGk": base64), [72, 105]);
+    assert_eq!(bytify!("SGVsbG8=": base64), *b"Hello");
+    assert_eq!(bytify!("SGVsbG8h": base64), *b"Hello!");
+    assert_eq!(bytify!("SA==": base64), [72]);
+    assert_eq!(bytify!("+/8=": base64), [0xFB, 0xFF]);
+    assert_eq!(bytify!("-_8": base64url), [0xFB, 0xFF]);
+    let empty: [u8; 0] = bytify!("": base64);
+    assert_eq!(empty, []);
+}
+
+#[test]
+fn include() {
+    assert_eq!(bytify!(include("data/include.bin")), [b'H', b'i', b'!', 0x00, 0xFF]);
+    assert_eq!(bytify!(0x02u8, include("data/include.bin"), 0xAABBu16: BE), [0x02, b'H', b'i', b'!', 0x00, 0xFF, 0xAA, 0xBB]);
+    assert_eq!(&bytify!(include("data/include.bin"))[..], &include_bytes!("data/include.bin")[..]);
+}
+
+#[test]
+fn include_tracked() {
+    // The expansion carries an `include_bytes!` of the file along, it stays a constant expression.
+    const INCLUDED: [u8; 5] = bytify!(include("data/include.bin"));
+    const LEN: usize = bytify_len!(include("data/include.bin"));
+    assert_eq!(INCLUDED, *include_bytes!("data/include.bin"));
+    assert_eq!(LEN, 5);
+    let bytes: &'static [u8] = &bytify!(include("data/include.bin"), 0x01u8);
+    assert_eq!(bytes, b"Hi!\x00\xFF\x01");
+}
+
+#[test]
+fn align() {
+    assert_eq!(bytify!(0x01u8, align(4)), [0x01, 0x00, 0x00, 0x00]);
+    assert_eq!(bytify!(0x01u8, align(4, 0xFF), 0x02u8), [0x01, 0xFF, 0xFF, 0xFF, 0x02]);
+    assert_eq!(bytify!(0x01u32, align(4), 0x02u8), [0x01, 0x00, 0x00, 0x00, 0x02]);
+    assert_eq!(bytify!(0x01u8, align(1)), [0x01]);
+    assert_eq!(bytify!("abc", align(8)).len(), 8);
+    assert_eq!(bytify!(0x01u8, align(2), 0x02u8, align(2)), [0x01, 0x00, 0x02, 0x00]);
+}
+
+#[test]
+fn reserved() {
+    assert_eq!(bytify!(reserved(4)), [0x00, 0x00, 0x00, 0x00]);
+    assert_eq!(bytify!(0x01u8, reserved(2), 0x02u8), [0x01, 0x00, 0x00, 0x02]);
+}
+
+#[test]
+fn pad_to() {
+    assert_eq!(bytify!(0x01u8, 0x02u8, pad_to(4)), [0x01, 0x02, 0x00, 0x00]);
+    assert_eq!(bytify!(0x01u8, pad_to(1), 0x02u8), [0x01, 0x02]);
+    assert_eq!(bytify!(pad_to(3)), [0x00, 0x00, 0x00]);
+    assert_eq!(bytify!("id", pad_to(8), 0xFFu8).len(), 9);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn env() {
+    assert_eq!(&bytify!(env("CARGO_PKG_NAME")), b"bytify");
+    assert_eq!(&bytify!(env("CARGO_PKG_VERSION"))[..], env!("CARGO_PKG_VERSION").as_bytes());
+    assert_eq!(bytify!(env("BYTIFY_UNDEFINED_VARIABLE", "0.0.0"): cstr), *b"0.0.0\0");
+    assert_eq!(bytify!(env("CARGO_PKG_NAME", "unused"): utf16be)[.. 4], [0x00, b'b', 0x00, b'y']);
+    // The expansion carries an `option_env!` of the variable along, it stays a constant expression.
+    const NAME: [u8; 6] = bytify!(env("CARGO_PKG_NAME"));
+    assert_eq!(NAME, *b"bytify");
+}
+
+#[test]
+fn fill() {
+    assert_eq!(bytify!(fill(0xAA; 3)), [0xAA, 0xAA, 0xAA]);
+    assert_eq!(bytify!(0x01u8, fill(0x00; 4), 0x02u8), [0x01, 0x00, 0x00, 0x00, 0x00, 0x02]);
+    assert_eq!(bytify!(fill(0xFFu8, 2)), [0xFF, 0xFF]);
+    let empty: [u8; 0] = bytify!(fill(0x00; 0));
+    assert_eq!(empty, []);
+    let mut buffer = Vec::new();
+    bytify_into!(buffer, fill(0xAA; 2), 0x01u8);
+    assert_eq!(buffer, [0xAA, 0xAA, 0x01]);
+}
+
+#[test]
+fn float_bits() {
+    assert_eq!(bytify!(f32_bits(0x3F800000)), bytify!(1.0f32));
+    assert_eq!(bytify!(f32_bits(0x3F800000u32): BE), bytify!(1.0f32: BE));
+    assert_eq!(bytify!(f64_bits(0x3FF0_0000_0000_0000u64)), bytify!(1.0f64));
+    assert_eq!(bytify!(f32_bits(0x7FC00001)), [0x01, 0x00, 0xC0, 0x7F]);
+    assert_eq!(bytify!(f64_bits(0x1)), [0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+}
+
+#[test]
+fn sign_magnitude() {
+    assert_eq!(bytify!(-1i8), [0xFF]);
+    assert_eq!(bytify!(sign_magnitude(-1i8)), [0x81]);
+    assert_eq!(bytify!(sign_magnitude(1i8), sign_magnitude(-127i8)), [0x01, 0xFF]);
+    assert_eq!(bytify!(sign_magnitude(-0x1234i16): BE), [0x92, 0x34]);
+    assert_eq!(bytify!(sign_magnitude(-1i32)), [0x01, 0x00, 0x00, 0x80]);
+    assert_eq!(bytify!(sign_magnitude(-5), sign_magnitude(-200)), [0x85, 0xC8, 0x80]);
+    assert_eq!(bytify!(sign_magnitude(-(2 + 3)) : BE), [0x85]);
+}
+
+#[test]
+fn bcd() {
+    assert_eq!(bytify!(bcd(1234)), [0x12, 0x34]);
+    assert_eq!(bytify!(bcd(0), bcd(7)), [0x00, 0x07]);
+    assert_eq!(bytify!(bcd(123)), [0x01, 0x23]);
+    assert_eq!(bytify!(bcd(123, 0xF)), [0xF1, 0x23]);
+    assert_eq!(bytify!(bcd(4_915_551_234u64, 0x0)), [0x49, 0x15, 0x55, 0x12, 0x34]);
+}
+
+#[test]
+fn neg_group() {
+    assert_eq!(bytify!(@be; neg_group(0x0001u16: BE)), [0xFF, 0xFF]);
+    assert_eq!(bytify!(neg_group(0x0001u16): BE), [0xFF, 0xFF]);
+    assert_eq!(bytify!(neg_group(0x0001u16)), [0xFF, 0xFF]);
+    assert_eq!(bytify!(neg_group(0x0001u16: BE)), [0xFF, 0xFF]);
+    assert_eq!(bytify!(neg_group(0x0002u16): BE), [0xFF, 0xFE]);
+    assert_eq!(bytify!(neg_group(0x0002u16: BE)), [0xFF, 0xFE]);
+    assert_eq!(bytify!(neg_group(0x01u8, 0x0002u16: BE, be { 0x03u8 })), [0xFE, 0xFF, 0xFD, 0xFD]);
+    assert_eq!(bytify!(neg_group(0x0002u16: LE, le { 0x00u8 })), [0xFE, 0xFF, 0xFF]);
+    assert_eq!(bytify!(neg_group(0x01u8, 0x02u8): BE), [0xFE, 0xFE]);
+    assert_eq!(bytify!(neg_group(0x00u8, 0x00u8)), [0x00, 0x00]);
+    assert_eq!(bytify!(0xAAu8, neg_group([0x00u8; 3], 0x80u8), 0xAAu8), [0xAA, 0x00, 0x00, 0x00, 0x80, 0xAA]);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn checksum() {
+    assert_eq!(&bytify!("123456789", sum8())[9 ..], &[0xDD]);
+    assert_eq!(&bytify!("123456789", sum16_le())[9 ..], &[0xDD, 0x01]);
+    assert_eq!(&bytify!("123456789", sum16_be())[9 ..], &[0x01, 0xDD]);
+    assert_eq!(&bytify!("123456789", crc16_ccitt(): BE)[9 ..], &[0x29, 0xB1]);
+    assert_eq!(&bytify!("123456789", crc32())[9 ..], &[0x26, 0x39, 0xF4, 0xCB]);
+    assert_eq!(&bytify!(@be; "123456789", crc32())[9 ..], &[0xCB, 0xF4, 0x39, 0x26]);
+    assert_eq!(bytify!(crc16_ccitt(), crc32()), [0xFF, 0xFF, 0x00, 0x00, 0xFF, 0xFF]);
+    assert_eq!(bytify!(0xFFu8, 0x02u8, sum8(), sum8()), [0xFF, 0x02, 0x01, 0x02]);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn sha256() {
+    assert_eq!(&bytify!("abc", sha256())[3 ..], &[
+        0xBA, 0x78, 0x16, 0xBF, 0x8F, 0x01, 0xCF, 0xEA, 0x41, 0x41, 0x40, 0xDE, 0x5D, 0xAE, 0x22, 0x23,
+        0xB0, 0x03, 0x61, 0xA3, 0x96, 0x17, 0x7A, 0x9C, 0xB4, 0x10, 0xFF, 0x61, 0xF2, 0x00, 0x15, 0xAD,
+    ]);
+    assert_eq!(bytify!(sha256(): BE), [
+        0xE3, 0xB0, 0xC4, 0x42, 0x98, 0xFC, 0x1C, 0x14, 0x9A, 0xFB, 0xF4, 0xC8, 0x99, 0x6F, 0xB9, 0x24,
+        0x27, 0xAE, 0x41, 0xE4, 0x64, 0x9B, 0x93, 0x4C, 0xA4, 0x95, 0x99, 0x1B, 0x78, 0x52, 0xB8, 0x55,
+    ]);
+    // A whole block of message forces the padding into a second one.
+    assert_eq!(&bytify!(0x01u8, 0x02u8, 0x03u8, "x" * 61, sha256())[64 .. 68], &[0x37, 0x74, 0x69, 0xE8]);
+    assert_eq!(&bytify!("a" * 200, sha256())[200 .. 204], &[0xC2, 0xA9, 0x08, 0xD9]);
+    assert_eq!(bytify!("abc", sha256(), crc32()).len(), 39);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn length_delimited_block() {
+    assert_eq!(bytify!(block_u16_be { 0x01u8, "hi" }), [0x00, 0x03, 0x01, b'h', b'i']);
+    assert_eq!(bytify!(block_u8 {}, block_u32_le { 0xFFFFu16 }), [0x00, 0x02, 0x00, 0x00, 0x00, 0xFF, 0xFF]);
+    assert_eq!(bytify!(block_u8 { 0x01u8, block_u8 { 0x02u8 } }), [0x03, 0x01, 0x01, 0x02]);
+    assert_eq!(bytify!(0xAAu8, block_u16_le { #VERSION: u16 BE }), [0xAA, 0x02, 0x00, 0x01, 0x02]);
+    assert_eq!(bytify!(block_u8 { [0u8; 255] }).len(), 256);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn type_length_value() {
+    assert_eq!(bytify!(tlv(type: 0x01u8, { 0x0A0Bu16: BE, "ok" })), [0x01, 0x04, 0x0A, 0x0B, b'o', b'k']);
+    assert_eq!(bytify!(tlv_u16_le(type: 0x0102u16: BE, {})), [0x01, 0x02, 0x00, 0x00]);
+    assert_eq!(bytify!(tlv_u16_be(0x05u8, { 0xFFu8, tlv(type: 0x06u8, { 0x07u8 }) })), [0x05, 0x00, 0x04, 0xFF, 0x06, 0x01, 0x07]);
+    assert_eq!(bytify!(tlv_u32_be(type: 0x10u8, { #VERSION: u16 LE })), [0x10, 0x00, 0x00, 0x00, 0x02, 0x02, 0x01]);
+}
+
+#[test]
+fn varint() {
+    assert_eq!(bytify!(varint(0)), [0x00]);
+    assert_eq!(bytify!(varint(1u8), varint(127)), [0x01, 0x7F]);
+    assert_eq!(bytify!(varint(128)), [0x80, 0x01]);
+    assert_eq!(bytify!(varint(300u32)), [0xAC, 0x02]);
+    assert_eq!(bytify!(varint(624485): BE), [0xE5, 0x8E, 0x26]);
+    assert_eq!(bytify!(varint(0xFFFFFFFFu32)), [0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+    assert_eq!(bytify!(varint(0xFFFFFFFFFFFFFFFFu64)), [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]);
+    assert_eq!(bytify!(varint(1u128 << 127)).len(), 19);
+}
+
+#[test]
+fn svarint() {
+    assert_eq!(bytify!(svarint(0), svarint(-1), svarint(1), svarint(-2), svarint(2)), [0x00, 0x01, 0x02, 0x03, 0x04]);
+    assert_eq!(bytify!(svarint(-64), svarint(64i32)), [0x7F, 0x80, 0x01]);
+    assert_eq!(bytify!(svarint(2147483647i32)), [0xFE, 0xFF, 0xFF, 0xFF, 0x0F]);
+    assert_eq!(bytify!(svarint(-2147483648i32)), [0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+    assert_eq!(bytify!(svarint(-0x8000_0000_0000_0000_0000_0000_0000_0000i128)).len(), 19);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn fixed_field() {
+    assert_eq!(bytify!(field("AB", size: 8, fill: 0x20, align: left)), *b"AB      ");
+    assert_eq!(bytify!(field("AB", size: 8, fill: 0x20, align: right)), *b"      AB");
+    assert_eq!(bytify!(field("AB", size: 4)), [b'A', b'B', 0x00, 0x00]);
+    assert_eq!(bytify!(field(b"ABCD", size: 4, fill: 0x2E)), *b"ABCD");
+    assert_eq!(bytify!(field("ABCDEF", size: 4, overflow: truncate)), *b"ABCD");
+    assert_eq!(bytify!(field(0x0102u16: BE, size: 4, align: right)), [0x00, 0x00, 0x01, 0x02]);
+    assert_eq!(bytify!(field("é": utf16le, size: 3, fill: 0xFF,)), [0xE9, 0x00, 0xFF]);
+    assert_eq!(bytify!(0xAAu8, field(#VERSION: u16 BE, size: 3, align: right)), [0xAA, 0x00, 0x01, 0x02]);
+}
+
+#[derive(Bytify)]
+#[bytify(endian = "be")]
+struct MixedHeader {
+    magic: [u8; 4],
+    version: u16,
+    #[bytify(endian = "le")]
+    length: u32,
+    offset: i16,
+    #[bytify(endian = "little")]
+    scale: f32,
+    ports: [u16; 2],
+}
+
+#[derive(Bytify)]
+struct Pair(u8, #[bytify(endian = "be")] u16, u16);
+
+#[test]
+fn derive() {
+    let header = MixedHeader {
+        magic: *b"BYTE",
+        version: 0x0102,
+        length: 0x03040506,
+        offset: -2,
+        scale: 1.5,
+        ports: [80, 443],
+    };
+    assert_eq!(header.to_bytes(), bytify!(
+        b"BYTE", 0x0102u16: BE, 0x03040506u32: LE, -2i16: BE, 1.5f32: LE, 80u16: BE, 443u16: BE,
+    ));
+    assert_eq!(Pair(0xFF, 0x0102, 0x0304).to_bytes(), bytify!(0xFFu8, 0x0102u16: BE, 0x0304u16));
+}
+
+#[test]
+fn wide_c_string() {
+    assert_eq!(bytify!("Path": wcstr), [b'P', 0x00, b'a', 0x00, b't', 0x00, b'h', 0x00, 0x00, 0x00]);
+    assert_eq!(bytify!("Hi": wcstr_be), [0x00, b'H', 0x00, b'i', 0x00, 0x00]);
+    assert_eq!(bytify!("C:\\": wcstr), bytify!("C:\\": utf16le: cstr));
+    assert_eq!(bytify!("": wcstr, "é": wcstr), [0x00, 0x00, 0xE9, 0x00, 0x00, 0x00]);
+}
+
+#[test]
+fn fill_pattern() {
+    assert_eq!(bytify!(fill_pattern([0xDE, 0xAD]; 3)), [0xDE, 0xAD, 0xDE, 0xAD, 0xDE, 0xAD]);
+    assert_eq!(bytify!(0x01u8, fill_pattern([0xAAu8, 0x55, 0x00], 2), 0x02u8).len(), 8);
+    assert_eq!(bytify!(fill_pattern(b"ab"; 2), fill_pattern([0xFF]; 0)), *b"abab");
+    assert_eq!(bytify_len!(fill_pattern([0xDE, 0xAD, 0xBE, 0xEF]; 16)), 64);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn bom_directives() {
+    assert_eq!(bytify!(bom_utf8()), [0xEF, 0xBB, 0xBF]);
+    assert_eq!(bytify!(bom_utf16le()), [0xFF, 0xFE]);
+    assert_eq!(bytify!(bom_utf16be()), [0xFE, 0xFF]);
+    assert_eq!(bytify!(bom_utf32le()), [0xFF, 0xFE, 0x00, 0x00]);
+    assert_eq!(bytify!(bom_utf32be()), [0x00, 0x00, 0xFE, 0xFF]);
+    assert_eq!(bytify!(bom_utf16le(): BE, "hi": utf16le), bytify!("hi": utf16le_bom));
+    assert_eq!(bytify!(bom_utf8(), "hi"), bytify!("hi": utf8_bom));
+}
+
+#[test]
+fn mirror() {
+    assert_eq!(bytify!(mirror(0x1234u16)), [0x34, 0x12, 0x12, 0x34]);
+    assert_eq!(bytify!(mirror_be(0x1234u16)), [0x12, 0x34, 0x34, 0x12]);
+    assert_eq!(bytify!(@be; mirror(0x01020304u32)), [0x04, 0x03, 0x02, 0x01, 0x01, 0x02, 0x03, 0x04]);
+    assert_eq!(bytify!(mirror(-2i16), mirror(0xAB)), [0xFE, 0xFF, 0xFF, 0xFE, 0xAB, 0xAB]);
+    assert_eq!(bytify!(mirror(-1): i16, mirror(0x100 + 1)), [0xFF, 0xFF, 0xFF, 0xFF, 0x01, 0x01, 0x01, 0x01]);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn ashex() {
+    assert_eq!(bytify!(ashex(0xDEu8, 0xADu8)), *b"DEAD");
+    assert_eq!(bytify!(ashex_lower(0xDEu8, 0xADu8)), *b"dead");
+    assert_eq!(bytify!(ashex(0xBEEFu16: BE, "\n")), *b"BEEF0A");
+    assert_eq!(bytify!(ashex(0xBEEFu16)), *b"EFBE");
+    assert_eq!(bytify!(b'<', ashex(), b'>'), *b"<>");
+}
+
+#[test]
+fn bitflags() {
+    assert_eq!(bytify!(bitflags(true, false, true)), [0b0000_0101]);
+    assert_eq!(bytify!(bitflags_msb(true, false, true)), [0b1010_0000]);
+    assert_eq!(
+        bytify!(bitflags(true, true, true, true, true, true, true, true, false, true)),
+        [0b1111_1111, 0b0000_0010]
+    );
+    assert_eq!(bytify!(bitflags_msb(false, false, false, false, false, false, false, true, true)), [0b0000_0001, 0b1000_0000]);
+    assert_eq!(bytify!(0xAAu8, bitflags(), 0xBBu8), [0xAA, 0xBB]);
+}
+
+#[test]
+fn guid() {
+    assert_eq!(
+        bytify!(guid("550e8400-e29b-41d4-a716-446655440000")),
+        [0x55, 0x0E, 0x84, 0x00, 0xE2, 0x9B, 0x41, 0xD4, 0xA7, 0x16, 0x44, 0x66, 0x55, 0x44, 0x00, 0x00]
+    );
+    assert_eq!(
+        bytify!(guid_ms("{00112233-4455-6677-8899-AABBCCDDEEFF}")),
+        [0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]
+    );
+    assert_eq!(bytify!(guid("00000000-0000-0000-0000-000000000000"), 1u8), bytify!([0u8; 16], 1u8));
+}
+
+#[test]
+fn ip() {
+    assert_eq!(bytify!(ipv4("192.168.0.1")), [192, 168, 0, 1]);
+    assert_eq!(bytify!(ipv6("::1")), [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(
+        bytify!(ipv6("2001:db8::ff00:42:8329")),
+        [0x20, 0x01, 0x0D, 0xB8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0x00, 0x00, 0x42, 0x83, 0x29]
+    );
+    assert_eq!(bytify!(ipv4("127.0.0.1"), 8080u16: BE), [127, 0, 0, 1, 0x1F, 0x90]);
+}
+
+#[test]
+fn fixed_point() {
+    assert_eq!(bytify!(fixed_q8_8(1.5): BE), [0x01, 0x80]);
+    assert_eq!(bytify!(fixed_q8_8(-1.5): BE), [0xFE, 0x80]);
+    assert_eq!(bytify!(fixed_q8_8(1)), [0x00, 0x01]);
+    assert_eq!(bytify!(fixed_q8_8(127.99609375): BE, fixed_q8_8(-128.0): BE), [0x7F, 0xFF, 0x80, 0x00]);
+    assert_eq!(bytify!(fixed_q16_16(-0.5): BE), [0xFF, 0xFF, 0x80, 0x00]);
+    assert_eq!(bytify!(fixed_q16_16(3.14159)), 205887i32.to_le_bytes());
+    assert_eq!(bytify!(fixed_q1_7(0.5), fixed_q0_8(-0.0)), [0x40, 0x00]);
+    assert_eq!(bytify!(fixed_q32_32(1.0): BE), 1i64.wrapping_shl(32).to_be_bytes());
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn assert_len() {
+    assert_eq!(bytify!(assert_len(4), 0x01u8, "ab", 0x02u8), [0x01, b'a', b'b', 0x02]);
+    assert_eq!(bytify!(0xCAFEu16: BE, align(8), assert_len(8)), [0xCA, 0xFE, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    assert_eq!(bytify_len!(assert_len(2), 1u16), 2);
+    let empty: [u8; 0] = bytify!(assert_len(0));
+    assert_eq!(empty, []);
+}
+
+#[test]
+fn signed_min() {
+    assert_eq!(bytify!(-128i8), [0x80]);
+    assert_eq!(bytify!(-128), [0x80]);
+    assert_eq!(bytify!(-32768i16: BE), [0x80, 0x00]);
+    assert_eq!(bytify!(-2147483648i32: BE), i32::MIN.to_be_bytes());
+    assert_eq!(bytify!(-9223372036854775808i64: BE), i64::MIN.to_be_bytes());
+    assert_eq!(bytify!(-170141183460469231731687303715884105728i128: BE), i128::MIN.to_be_bytes());
+    assert_eq!(bytify!(-129), (-129i16).to_le_bytes());
+}
+
+#[test]
+fn endianness_block() {
+    assert_eq!(bytify!(be { 1u16, 2u32, 3u16 }, le { 4u16 }), [
+        0x00, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x03, 0x04, 0x00,
+    ]);
+    assert_eq!(bytify!(BE { 1u16, 2u16: LE, [3u16, 4u16] }), [0x00, 0x01, 0x02, 0x00, 0x00, 0x03, 0x00, 0x04]);
+    assert_eq!(bytify!(be { 1u16, le { 2u16, be { 3u16 } }, }), [0x00, 0x01, 0x02, 0x00, 0x00, 0x03]);
+    assert_eq!(bytify!(be { fill(0xAA; 2), "hi": utf16le }), [0xAA, 0xAA, b'h', 0x00, b'i', 0x00]);
+    let empty: [u8; 0] = bytify!(be {});
+    assert_eq!(empty, []);
+    assert_eq!(bytify!(be { b"ab", b'c', true, 1u16 }), [b'a', b'b', b'c', 0x01, 0x00, 0x01]);
+}
+
+mod constants {
+    pub const MINOR: u8 = 7;
+}
+
+const VERSION: u16 = 0x0102;
+const SCALE: f32 = 1.5;
+const HEADER: [u8; 8] = bytify!(b"AB", #VERSION: u16 BE, #VERSION: u16, #constants::MINOR: u8, #(VERSION as u8 + 1): u8);
+
+#[test]
+fn default_endianness() {
+    assert_eq!(bytify!(@be; 1u16, 2u16), [0x00, 0x01, 0x00, 0x02]);
+    assert_eq!(bytify!(@be; 1u16: LE, 2u16, [3u16: le]), [0x01, 0x00, 0x00, 0x02, 0x03, 0x00]);
+    assert_eq!(bytify!(@le; 1u16, 2u16: BE), [0x01, 0x00, 0x00, 0x02]);
+    assert_eq!(bytify!(@utf16be; "a", 'b'), [0x00, b'a', 0x00, b'b']);
+    assert_eq!(bytify_len!(@be; 1u32), 4);
+    let mut buffer = Vec::new();
+    bytify_into!(buffer, @be; 0x0102u16, #VERSION: u16);
+    assert_eq!(buffer, [0x01, 0x02, 0x01, 0x02]);
+}
+
+#[test]
+fn interpolation() {
+    assert_eq!(HEADER, [b'A', b'B', 0x01, 0x02, 0x02, 0x01, 0x07, 0x03]);
+    assert_eq!(bytify!(#SCALE: f32, #(SCALE as f64): f64 be), [&1.5f32.to_le_bytes()[..], &1.5f64.to_be_bytes()[..]].concat()[..]);
+    assert_eq!(bytify!(be { #VERSION: u16, #VERSION: u16 le }), [0x01, 0x02, 0x02, 0x01]);
+    assert_eq!(bytify!([#VERSION: u16 BE; 2], 0xFFu8), [0x01, 0x02, 0x01, 0x02, 0xFF]);
+    assert_eq!(bytify!([0x00u8, #VERSION: u16]: BE), [0x00, 0x01, 0x02]);
+    let runtime = 0xAABBCCDDu32;
+    assert_eq!(bytify!(#runtime: u32 BE), [0xAA, 0xBB, 0xCC, 0xDD]);
+}
+
+#[test]
+fn discriminant() {
+    #[allow(dead_code)]
+    #[repr(u16)]
+    enum Kind {
+        A = 1,
+        B = 0x0203,
+    }
+    const PACKET: [u8; 3] = bytify!(discriminant(Kind::A as u8), discriminant(Kind::B as u16): BE);
+    assert_eq!(PACKET, [0x01, 0x02, 0x03]);
+    assert_eq!(bytify!(be { discriminant(Kind::B as u32) }), [0x00, 0x00, 0x02, 0x03]);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn len() {
+    assert_eq!(bytify_len!(), 0);
+    assert_eq!(bytify_len!(1u8, 2u16, "hi", 0.5, 1e40f64), bytify!(1u8, 2u16, "hi", 0.5, 1e40f64).len());
+    assert_eq!(bytify_len!(#VERSION: u16 BE, fill(0x00; 3), align(8)), 8);
+    let buffer: [u8; bytify_len!("abc": cstr, -1i32: BE)] = bytify!("abc": cstr, -1i32: BE);
+    assert_eq!(buffer, [b'a', b'b', b'c', 0x00, 0xFF, 0xFF, 0xFF, 0xFF]);
+}
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn len_dimension() {
+    #[repr(C)]
+    struct Record {
+        magic: [u8; bytify_len!("RIFF", 0u32)],
+        data: [u8; bytify_len!(fill(0x00; 3))],
+    }
+    const _: () = assert!(bytify_len!("RIFF", 0u32) == 8);
+    const LEN: usize = bytify_len!(0x01u8, 0x0203u16);
+    assert_eq!(std::mem::size_of::<Record>(), 11);
+    assert_eq!([0u8; LEN], [0x00, 0x00, 0x00]);
+}
+
+bytify_const!(PACKET: 0x01u8, 0x02u8, "ab");
+bytify_const!(PORT: @net; 8080u16);
+bytify_const!(
+    /// Interpolated values are allowed in a constant as well.
+    pub(crate) VERSIONED: "v", #VERSION: u16 BE,
+);
+
+#[cfg(not(any(feature = "default-utf16le", feature = "default-utf16be")))]
+#[test]
+fn constant() {
+    assert_eq!(PACKET, [0x01, 0x02, b'a', b'b']);
+    assert_eq!(PACKET.len(), bytify_len!(0x01u8, 0x02u8, "ab"));
+    assert_eq!(VERSIONED, [b'v', 0x01, 0x02]);
+    assert_eq!(PORT, [0x1F, 0x90]);
+}
+
+#[test]
+fn structure() {
+    assert_eq!(bytify_struct! { version: u16 = 1, flags: u8 = 0x03 }, [0x01, 0x00, 0x03]);
+    assert_eq!(bytify_struct! { flags: u8 = 0x03, version: u16 = 1 }, [0x03, 0x01, 0x00]);
+    assert_eq!(
+        bytify_struct! { @be; magic: u32 = 0xCAFE, len: u16 LE = 2, delta: i8 = -1, ratio: f64 = 1 },
+        bytify!(0xCAFEu32: BE, 2u16: LE, -1i8, 1.0f64: BE)
+    );
+    assert_eq!(bytify_struct! { version: u16 BE = VERSION, minor: u8 = constants::MINOR }, [0x01, 0x02, 7]);
+    assert_eq!(bytify_struct! { mask: u16 = (1 << 4) | 1, scale: f32 = -0.5f32, }, bytify!(0x11u16, -0.5f32));
+    let empty: [u8; 0] = bytify_struct! {};
+    assert_eq!(empty, []);
+}
@@ -0,0 +1,49 @@
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use bytify::bytify_into;
+use bytify::{bytify, bytify_len, bytify_ref};
+
+const VERSION: u16 = 0x0102;
+
+// `Vec` is not in scope of the derived method.
+#[cfg(feature = "alloc")]
+mod version {
+    use bytify::Bytify;
+
+    #[derive(Bytify)]
+    #[bytify(endian = "be")]
+    pub struct Version {
+        pub major: u8,
+        pub minor: u16,
+    }
+}
+
+// UTF-8 is given explicitly, the default encoding may be changed by the features.
+const HEADER: [u8; bytify_len!("BM": utf8, #VERSION: u16 BE, 0.5f32)] = bytify!("BM": utf8, #VERSION: u16 BE, 0.5f32);
+
+#[test]
+fn no_std() {
+    assert_eq!(HEADER, [b'B', b'M', 0x01, 0x02, 0x00, 0x00, 0x00, 0x3F]);
+    assert_eq!(bytify_ref!(1u8, 2u8), &[1, 2][..]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn no_std_into() {
+    let mut buffer = Vec::new();
+    bytify_into!(buffer, "hi": utf16be, #VERSION: u16);
+    assert_eq!(buffer, [0x00, b'h', 0x00, b'i', 0x02, 0x01]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn no_std_derive() {
+    let version = version::Version { major: 1, minor: 2 };
+    assert_eq!(version.to_bytes(), [0x01, 0x00, 0x02]);
+}
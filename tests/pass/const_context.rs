@@ -0,0 +1,15 @@
+use bytify::{bytify, bytify_len};
+
+const VERSION: u16 = 0x0102;
+
+static HEADER: [u8; 6] = bytify!("BM", 0x1000u32: BE);
+static SIZED: [u8; bytify_len!("BM", #VERSION: u16 BE)] = bytify!("BM", #VERSION: u16 BE);
+const SLICE: &[u8] = &bytify!("hi": cstr, -1i16, 0.5f32: be, fill(0xAA; 2), align(4));
+const INTERPOLATED: [u8; 4] = bytify!(#VERSION: u16, #(VERSION >> 8): u16 be);
+
+fn main() {
+    assert_eq!(HEADER, [b'B', b'M', 0x00, 0x00, 0x10, 0x00]);
+    assert_eq!(SIZED, [b'B', b'M', 0x01, 0x02]);
+    assert_eq!(SLICE.len(), 12);
+    assert_eq!(INTERPOLATED, [0x02, 0x01, 0x00, 0x01]);
+}
@@ -0,0 +1,19 @@
+#![cfg(feature = "trace")]
+
+use bytify::{bytify, bytify_len, bytify_trace};
+
+#[test]
+fn trace() {
+    // The table is only recorded alongside, the bytes are the same.
+    assert_eq!(bytify!(1u8, 0x0203u16: BE, b"hi", assert_len(5)), [0x01, 0x02, 0x03, b'h', b'i']);
+    assert_eq!(bytify_len!([0u8; 3], align(4)), 4);
+    assert_eq!(
+        bytify_trace!(1u8, 0x0203u16: BE, b"hi", assert_len(5)),
+        "element 0: offset 0, length 1\n\
+         element 1: offset 1, length 2\n\
+         element 2: offset 3, length 2\n\
+         element 3: offset 5, length 0\n",
+    );
+    assert_eq!(bytify_trace!([0u8; 3], align(4)), "element 0: offset 0, length 3\nelement 1: offset 3, length 1\n");
+    assert_eq!(bytify_trace!(), "");
+}
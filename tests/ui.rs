@@ -0,0 +1,9 @@
+// The expected diagnostics are written assuming UTF-8 as the default encoding, the cases use
+// every macro.
+#[cfg(all(feature = "alloc", not(any(feature = "default-utf16le", feature = "default-utf16be"))))]
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    t.pass("tests/pass/*.rs");
+}
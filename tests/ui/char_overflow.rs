@@ -0,0 +1,7 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!('Я': u8);
+    let _ = bytify!('😀': u16 LE);
+    let _ = bytify!("€100": latin1);
+}
@@ -0,0 +1,8 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(0x1234u16: BE: LE);
+    let _ = bytify!(0x1234u16 be: be);
+    let _ = bytify!('A': u16 LE: BE);
+    let _ = bytify!(be { 1u16 }: le);
+}
@@ -0,0 +1,7 @@
+use bytify::{bytify, bytify_struct};
+
+fn main() {
+    let _ = bytify!(1u8, 2u8, "three", 4.0, 'x'suffix, 6u8);
+    let _ = bytify!(["nested", 0x1FFu8], 0u8);
+    let _ = bytify_struct! { version: u16 = 1, flags: u8 = 0x100 };
+}
@@ -0,0 +1,5 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(env("BYTIFY_UNDEFINED_VARIABLE"));
+}
@@ -0,0 +1,7 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(include("missing.bin"));
+    // A file included before the failing one.
+    let _ = bytify!(include("../data/include.bin"), include("missing.bin"));
+}
@@ -0,0 +1,8 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(1u8, -1u8);
+    let _ = bytify!(1e40f32);
+    let _ = bytify!(65520.0f16);
+    let _ = bytify!(-65520.0f16: BE);
+}
@@ -0,0 +1,12 @@
+use bytify::{bytify, bytify_struct};
+
+fn main() {
+    let _ = bytify!(0.1f32: f32_strict);
+    let _ = bytify!(-0.1: f32_strict);
+    let _ = bytify!(f32_strict { 0.5, 16777217.0 });
+    let _ = bytify!((0.2 as f32): f32_strict);
+    let _ = bytify_struct!(@f32_strict; scale: f32 = 0.3);
+    let _ = bytify!(-0.1: BE);
+    let _ = bytify!(16777217.0);
+    let _ = bytify!(1e40);
+}
@@ -0,0 +1,10 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(1u8, -170141183460469231731687303715884105729);
+    let _ = bytify!(-40000: i16);
+    let _ = bytify!(@i8; -1, -129);
+    let _ = bytify!(i32 { -1, -(0x80000001) });
+    let _ = bytify!(300: u8);
+    let _ = bytify!(parse("70000", base: 10): u16 BE);
+}
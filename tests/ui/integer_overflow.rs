@@ -0,0 +1,12 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(0x1FFu8);
+    let _ = bytify!(65536u16);
+    let _ = bytify!(0x1_0000_0000u32);
+    let _ = bytify!(1u8, 0xFFFFFFFFFFFFFFFFFFu64);
+    let _ = bytify!(ramp(250u8, 4, 2));
+    let _ = bytify!(varint(300u8));
+    let _ = bytify!(0x1FFu8 as u16);
+    let _ = bytify!((200i8 as u8) + 1);
+}
@@ -0,0 +1,6 @@
+use bytify::bytify;
+
+fn main() {
+    bytify!("a\0b": cstr);
+    bytify!("C:\0": wcstr);
+}
@@ -0,0 +1,10 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(200u8 + 56);
+    let _ = bytify!(1 / 0);
+    let _ = bytify!(1u8 + 1u16);
+    let _ = bytify!(1 + 1.0);
+    let _ = bytify!(1u8 << 8);
+    let _ = bytify!(0x100 | 1u8);
+}
@@ -0,0 +1,30 @@
+use bytify::Bytify;
+
+#[derive(Bytify)]
+enum Kind {
+    A,
+}
+
+#[derive(Bytify)]
+#[bytify(endian = "middle")]
+struct Middle {
+    value: u16,
+}
+
+#[derive(Bytify)]
+struct Width {
+    #[bytify(width = 2)]
+    value: u16,
+}
+
+#[derive(Bytify)]
+struct Text {
+    value: &'static str,
+}
+
+#[derive(Bytify)]
+struct Size {
+    value: usize,
+}
+
+fn main() {}
@@ -0,0 +1,42 @@
+use bytify::bytify;
+
+const VERSION: u16 = 1;
+
+fn main() {
+    let _ = bytify!(0x01u8, align(3));
+    let _ = bytify!(0x01u8, align(0));
+    let _ = bytify!(0x01u8, align(4u8));
+    let _ = bytify!(0x01u8, align(4, 0x100));
+    let _ = bytify!(fill(-1; 4));
+    let _ = bytify!(fill(0x00; 4u32));
+    let _ = bytify!(reserved(0));
+    let _ = bytify!(block_u8 { [0u8; 256] });
+    let _ = bytify!(tlv(type: 0x01u8, { [0u8; 256] }));
+    let _ = bytify!(varint(-1));
+    let _ = bytify!(svarint(1u8));
+    let _ = bytify!(field("ABC", size: 2));
+    let _ = bytify!(fill_pattern([]; 2));
+    let _ = bytify!(fill_pattern([0x100]; 2));
+    let _ = bytify!(fill_pattern(0xDE; 2));
+    let _ = bytify!(field("AB", fill: 0x20));
+    let _ = bytify!(field("AB", size: 4, align: center));
+    let _ = bytify!(field("AB", size: 4, width: 2));
+    let _ = bytify!(field(#VERSION: u16, size: 1, overflow: truncate));
+    let _ = bytify!(parse("12", 37));
+    let _ = bytify!(ramp(0u8, 4, 1u16));
+    let _ = bytify!(env("CARGO_PKG_NAME", 0));
+    let _ = bytify!(0x0102u16, pad_to(1));
+    let _ = bytify!(bitflags(true, 1));
+    let _ = bytify!(fixed_q8_8(128.0));
+    let _ = bytify!(fixed_q8_8(-128.01));
+    let _ = bytify!(f32_bits(0x1_0000_0000));
+    let _ = bytify!(f32_bits(1u64));
+    let _ = bytify!(sign_magnitude(-128i8));
+    let _ = bytify!(sign_magnitude(1u8));
+    let _ = bytify!(bcd(-12));
+    let _ = bytify!(bcd(123, 0x1));
+    let _ = bytify!(neg_group(0x01u8, #VERSION: u16));
+    let _ = bytify!(neg_group(0x0001u16: BE, [0x0002u16: LE; 2]));
+    let _ = bytify!(#VERSION: u16, crc32());
+    let _ = bytify!(#VERSION: u16, sha256());
+}
@@ -0,0 +1,10 @@
+use bytify::bytify;
+
+enum Kind {
+    A,
+}
+
+fn main() {
+    let _ = bytify!(discriminant(Kind::A));
+    let _ = bytify!(discriminant(1 as u8));
+}
@@ -0,0 +1,16 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!("DEAD BEE": hex);
+    let _ = bytify!("DEAD BEEG": hex);
+    let _ = bytify!("SGk==": base64);
+    let _ = bytify!("S": base64);
+    let _ = bytify!("SG=k": base64);
+    let _ = bytify!("-_8": base64);
+    let _ = bytify!(guid("550e8400-e29b-41d4-a716-44665544000"));
+    let _ = bytify!(guid_ms("550e8400-e29b-41d4-a716-44665544000g"));
+    let _ = bytify!(ipv4("192.168.0.256"));
+    let _ = bytify!(ipv6("2001:db8:::1"));
+    let _ = bytify!(parse("12a", 10));
+    let _ = bytify!(parse("1000000000000000000000000000000000000000"));
+}
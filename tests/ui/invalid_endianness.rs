@@ -0,0 +1,7 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(1u8, 0xFACE: me);
+    let _ = bytify!(me { 0xFACE });
+    let _ = bytify!(@me; 0xFACE);
+}
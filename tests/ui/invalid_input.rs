@@ -0,0 +1,7 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(1u8 2u8);
+    let _ = bytify!(1u8:);
+    let _ = bytify!(#VERSION);
+}
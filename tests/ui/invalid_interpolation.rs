@@ -0,0 +1,7 @@
+use bytify::bytify;
+
+const FLAG: bool = true;
+
+fn main() {
+    let _ = bytify!(#FLAG: bool);
+}
@@ -0,0 +1,12 @@
+use bytify::bytify;
+
+const END: u8 = 4;
+
+fn main() {
+    let _ = bytify!('e'..='a');
+    let _ = bytify!(5..=3);
+    let _ = bytify!(0..);
+    let _ = bytify!(0..END);
+    let _ = bytify!(0.0..1.0);
+    let _ = bytify!(0u8..=3u16);
+}
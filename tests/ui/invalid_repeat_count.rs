@@ -0,0 +1,11 @@
+use bytify::bytify;
+
+const N: usize = 4;
+
+fn main() {
+    let _ = bytify!([0u8; -1]);
+    let _ = bytify!([0u8; N]);
+    let _ = bytify!([0u8; 4u32]);
+    let _ = bytify!("ab" * -2);
+    let _ = bytify!("ab" * 1.5);
+}
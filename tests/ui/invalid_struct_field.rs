@@ -0,0 +1,8 @@
+use bytify::bytify_struct;
+
+fn main() {
+    let _ = bytify_struct! { flags: u8 = 0x100 };
+    let _ = bytify_struct! { flags: u8 = 1u16 };
+    let _ = bytify_struct! { name: &str = "name" };
+    let _ = bytify_struct! { scale: f32 = 1.5f64 };
+}
@@ -0,0 +1,7 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(assert_len(20), "header", 0x01u32);
+    let _ = bytify!(0x01u8, assert_len(1), assert_len(2));
+    let _ = bytify!([assert_len(0)]);
+}
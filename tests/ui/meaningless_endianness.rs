@@ -0,0 +1,7 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(b"\xFF\x00": BE);
+    let _ = bytify!(1u16, b'A': le);
+    let _ = bytify!(true: NET);
+}
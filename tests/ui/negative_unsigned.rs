@@ -0,0 +1,9 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(-0xFFu8);
+    let _ = bytify!(-0x80u8);
+    let _ = bytify!(-0x1u16);
+    let _ = bytify!(-255u8);
+    let _ = bytify!(-1: u16);
+}
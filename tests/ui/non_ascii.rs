@@ -0,0 +1,6 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!("café": ascii);
+    let _ = bytify!('é': ascii);
+}
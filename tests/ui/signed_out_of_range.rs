@@ -0,0 +1,13 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(-129i8);
+    let _ = bytify!(128i8);
+    let _ = bytify!(-32769i16);
+    let _ = bytify!(-2147483649i32);
+    let _ = bytify!(-0x81i8);
+    let _ = bytify!(-0x100i8);
+    let _ = bytify!(-0x8001i16);
+    let _ = bytify!(-0o20000000001i32);
+    let _ = bytify!(-0b1000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0001i64);
+}
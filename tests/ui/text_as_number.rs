@@ -0,0 +1,9 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!('A' as u16);
+    let _ = bytify!("AB" as u8);
+    let _ = bytify!('A' as i64);
+    let _ = bytify!("ab"u16);
+    let _ = bytify!('A'u8);
+}
@@ -0,0 +1,7 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(300: u61);
+    let _ = bytify!(0xFACEu16: LEE);
+    let _ = bytify!("hi": len_u8_words);
+}
@@ -0,0 +1,7 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(1 as bool);
+    let _ = bytify!(1.5 as char: BE);
+    let _ = bytify!((1 as usize) as u8);
+}
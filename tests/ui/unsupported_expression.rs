@@ -0,0 +1,6 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(1u8, foo, 2u8);
+    let _ = bytify!(mirror(0x1234u16, 2));
+}
@@ -0,0 +1,6 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(1u8, "hi"suffix);
+    let _ = bytify!(1u8, b'a'suffix);
+}
@@ -0,0 +1,7 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(1u7);
+    let _ = bytify!(1.5f7);
+    let _ = bytify!(0x10u24: BE);
+}
@@ -0,0 +1,5 @@
+use bytify::bytify;
+
+fn main() {
+    let _ = bytify!(1u8, *1u8);
+}